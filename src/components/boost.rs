@@ -1,20 +1,40 @@
 use bevy::prelude::*;
 
+use crate::resources::BoostTuning;
+
 #[derive(Component)]
 pub struct EnergyBoost {
     pub energy: f32,
     pub is_boosting: bool,
+    /// Direction on the XZ plane the current boost pushes along, locked when it starts
+    pub direction: Vec3,
     pub cooldown_timer: Timer,
     pub recharge_timer: Timer,
+    /// Telegraph at the start of a boost: the duck brakes before it pushes off
+    pub windup_timer: Timer,
 }
 
-impl Default for EnergyBoost {
-    fn default() -> Self {
+impl EnergyBoost {
+    pub fn new(tuning: &BoostTuning) -> Self {
+        let mut windup_timer = Timer::from_seconds(tuning.windup, TimerMode::Once);
+        windup_timer.tick(windup_timer.duration());
         Self {
             energy: 1.0,
             is_boosting: false,
-            cooldown_timer: Timer::from_seconds(1.0, TimerMode::Once),
-            recharge_timer: Timer::from_seconds(2.0, TimerMode::Once),
+            direction: Vec3::NEG_Z,
+            cooldown_timer: Timer::from_seconds(tuning.cooldown, TimerMode::Once),
+            recharge_timer: Timer::from_seconds(tuning.recharge_delay, TimerMode::Once),
+            windup_timer,
         }
     }
-} 
\ No newline at end of file
+
+    /// Boosting, but still in the telegraph before the push.
+    pub fn is_winding_up(&self) -> bool {
+        self.is_boosting && !self.windup_timer.finished()
+    }
+
+    /// Boosting and past the telegraph, pushing along `direction`.
+    pub fn is_pushing(&self) -> bool {
+        self.is_boosting && self.windup_timer.finished()
+    }
+}
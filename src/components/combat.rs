@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+
+/// Brief loss of control after being knocked back: a stunned duck can't
+/// steer or start a boost, and slides wherever the hit sent it.
+#[derive(Component, Debug)]
+pub struct HitStun {
+    pub timer: Timer,
+}
+
+impl Default for HitStun {
+    fn default() -> Self {
+        let mut timer = Timer::from_seconds(0.0, TimerMode::Once);
+        timer.tick(timer.duration());
+        Self { timer }
+    }
+}
+
+impl HitStun {
+    pub fn is_stunned(&self) -> bool {
+        !self.timer.finished()
+    }
+
+    /// Stuns for `seconds`, unless the duck is already stunned for longer.
+    pub fn stun(&mut self, seconds: f32) {
+        if self.timer.remaining_secs() < seconds {
+            self.timer = Timer::from_seconds(seconds, TimerMode::Once);
+        }
+    }
+}
+
+/// Sent for every duck knocked back by a hit, so scoring and effects can
+/// react to shoves.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DuckPushed {
+    /// The duck that was knocked back
+    pub pushed: Entity,
+    /// The duck it collided with
+    pub pusher: Entity,
+    /// Velocity change applied to `pushed`
+    pub impulse: Vec3,
+    pub closing_speed: f32,
+    /// Whether `pusher` was boosting into it
+    pub rammed: bool,
+}
+
+/// The duck that last shoved this one. A fall while the credit window is
+/// still open counts as a knockout for that duck.
+#[derive(Component, Debug)]
+pub struct LastHitBy {
+    pub attacker: Option<Entity>,
+    /// Credit expires when this runs out
+    pub timer: Timer,
+}
+
+impl Default for LastHitBy {
+    fn default() -> Self {
+        Self {
+            attacker: None,
+            timer: Timer::from_seconds(0.0, TimerMode::Once),
+        }
+    }
+}
+
+impl LastHitBy {
+    /// Credits `attacker` for the next `window` seconds.
+    pub fn record(&mut self, attacker: Entity, window: f32) {
+        self.attacker = Some(attacker);
+        self.timer = Timer::from_seconds(window, TimerMode::Once);
+    }
+
+    /// Who gets the knockout, if anyone still has credit; clears it.
+    pub fn take_credit(&mut self) -> Option<Entity> {
+        let attacker = self.attacker.take();
+        attacker.filter(|_| !self.timer.finished())
+    }
+}
+
+/// Sent when a duck falls off within the credit window of being shoved.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DuckKnockedOut {
+    pub victim: Entity,
+    pub attacker: Entity,
+}
@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+
+/// Match standing of a duck. Every duck, player or AI, carries one.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Contestant {
+    pub name: String,
+    pub lives: u32,
+    pub points: i32,
+    pub falls: u32,
+    /// Falls of other ducks this duck gets credit for
+    pub knockouts: u32,
+    /// Knockouts that took a duck's last life
+    pub eliminations: u32,
+    /// Knockouts since this duck last fell
+    pub streak: u32,
+    pub best_streak: u32,
+}
+
+impl Contestant {
+    pub fn new(name: String, lives: u32) -> Self {
+        Self {
+            name,
+            lives,
+            points: 0,
+            falls: 0,
+            knockouts: 0,
+            eliminations: 0,
+            streak: 0,
+            best_streak: 0,
+        }
+    }
+
+    /// Costs the duck a life and a point, and ends its streak.
+    pub fn record_fall(&mut self) {
+        self.falls += 1;
+        self.lives = self.lives.saturating_sub(1);
+        self.points -= 1;
+        self.streak = 0;
+    }
+
+    /// Credits a knockout: `points`, plus `streak_bonus` for every earlier
+    /// knockout in the current streak.
+    pub fn record_knockout(&mut self, points: i32, streak_bonus: i32, eliminated: bool) {
+        self.knockouts += 1;
+        if eliminated {
+            self.eliminations += 1;
+        }
+        self.points += points + streak_bonus * self.streak as i32;
+        self.streak += 1;
+        self.best_streak = self.best_streak.max(self.streak);
+    }
+
+    pub fn is_eliminated(&self) -> bool {
+        self.lives == 0
+    }
+}
@@ -1,5 +1,9 @@
 use bevy::prelude::*;
-use bevy_rapier3d::prelude::{Collider, CollisionGroups, Group, RigidBody, Velocity, LockedAxes, Damping};
+use bevy_rapier3d::prelude::{
+    ActiveEvents, Collider, CollisionGroups, Damping, Group, LockedAxes, RigidBody, Velocity,
+};
+
+use super::{HitStun, LastHitBy};
 
 pub struct DuckParams {
     pub body_radius: f32,
@@ -14,46 +18,42 @@ pub struct DuckParams {
     pub is_player: bool,
 }
 
+/// Visual description of a duck, turned into meshes by the presentation layer.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct DuckModel {
+    pub body_radius: f32,
+    pub head_radius: f32,
+    pub bill_length: f32,
+    pub body_offset: Vec3,
+    pub head_offset: Vec3,
+    pub bill_offset: Vec3,
+    pub base_color: Color,
+    pub bill_color: Color,
+}
+
+/// Spawns the physics body of a duck. Meshes are attached separately so the
+/// simulation can run without a renderer.
 pub fn spawn_duck(
     commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<StandardMaterial>>,
     params: DuckParams,
 ) -> Entity {
-    let body_mesh = shape::UVSphere {
-        radius: params.body_radius,
-        ..default()
-    };
-    
-    let head_mesh = shape::UVSphere {
-        radius: params.head_radius,
-        ..default()
-    };
-
-    let bill_mesh = shape::Box {
-        min_x: 0.0,
-        max_x: params.bill_length,
-        min_y: -params.head_radius / 2.0,
-        max_y: params.head_radius / 2.0,
-        min_z: -params.head_radius / 2.0,
-        max_z: params.head_radius / 2.0,
-    };
-    
     let collision_group = if params.is_player {
         CollisionGroups::new(Group::GROUP_1, Group::GROUP_1 | Group::GROUP_2)
     } else {
         CollisionGroups::new(Group::GROUP_2, Group::GROUP_1 | Group::GROUP_2)
     };
 
-    let entity = commands.spawn((
-        PbrBundle {
-            mesh: meshes.add(body_mesh.into()),
-            material: materials.add(StandardMaterial {
-                base_color: params.base_color,
-                ..default()
-            }),
-            transform: Transform::from_translation(params.position + params.body_offset),
-            ..default()
+    commands.spawn((
+        SpatialBundle::from_transform(Transform::from_translation(params.position + params.body_offset)),
+        DuckModel {
+            body_radius: params.body_radius,
+            head_radius: params.head_radius,
+            bill_length: params.bill_length,
+            body_offset: params.body_offset,
+            head_offset: params.head_offset,
+            bill_offset: params.bill_offset,
+            base_color: params.base_color,
+            bill_color: params.bill_color,
         },
         RigidBody::Dynamic,
         Velocity::zero(),
@@ -68,31 +68,9 @@ pub fn spawn_duck(
             angular_damping: 0.5,
         },
         collision_group,
-    )).id();
-
-    commands.entity(entity).with_children(|parent| {
-        // Spawn head
-        parent.spawn(PbrBundle {
-            mesh: meshes.add(head_mesh.into()),
-            material: materials.add(StandardMaterial {
-                base_color: params.base_color,
-                ..default()
-            }),
-            transform: Transform::from_translation(params.body_offset + params.head_offset),
-            ..default()
-        });
-
-        // Spawn bill
-        parent.spawn(PbrBundle {
-            mesh: meshes.add(bill_mesh.into()),
-            material: materials.add(StandardMaterial {
-                base_color: params.bill_color,
-                ..default()
-            }),
-            transform: Transform::from_translation(params.body_offset + params.head_offset + params.bill_offset),
-            ..default()
-        });
-    });
-
-    entity
-}
\ No newline at end of file
+        // Duck-on-duck hits are read from collision events
+        ActiveEvents::COLLISION_EVENTS,
+        HitStun::default(),
+        LastHitBy::default(),
+    )).id()
+}
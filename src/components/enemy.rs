@@ -2,51 +2,128 @@ use bevy::prelude::*;
 use bevy::time::Timer;
 use rand::Rng;
 
-pub const PLATFORM_HEIGHT: f32 = 5.0;
-pub const WEAK_THRESHOLD: f32 = 30.0;
+use crate::resources::{Arena, EnemyTuning};
+
+/// Fraction of the arena's depth random platform spots keep from the edge.
+const RANDOM_SPOT_MARGIN: f32 = 0.45;
 
 #[derive(Component, Default)]
 pub struct Enemy {
     pub state: EnemyState,
     pub target_position: Option<Vec3>,
+    /// Time until the next utility re-evaluation; repeats every
+    /// `enemy.think_interval` of the tuning, scaled by difficulty
     pub state_timer: Timer,
     pub is_fallen: bool,
     pub respawn_timer: Timer,
-    pub health: f32,
     pub target: Option<Entity>,
-    pub target_timer: Timer, // Add target_timer field
+    /// Time until the target is re-picked; repeats every
+    /// `enemy.target_interval` of the tuning, scaled by difficulty
+    pub target_timer: Timer,
 }
 
-#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+/// The option an enemy's utility AI is currently acting on.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum EnemyState {
-    #[default]
-    Patrol,
+    /// Push the target duck towards the edge
     Chase,
+    /// Back away from the platform edge
+    Retreat,
+    /// Collect a nearby power-up coin
+    GrabPowerUp,
+    /// Boost straight into the target duck
+    Ram,
+    /// Drift back to the middle of the platform
+    #[default]
+    Recover,
     Fallen,
 }
 
+impl EnemyState {
+    /// The options scored on every decision, in tie-break order.
+    pub const OPTIONS: [EnemyState; 5] = [
+        EnemyState::Recover,
+        EnemyState::Chase,
+        EnemyState::Retreat,
+        EnemyState::GrabPowerUp,
+        EnemyState::Ram,
+    ];
+}
+
+/// How strongly an enemy favours each option of the utility AI. Rolled per
+/// enemy at spawn, so the pack doesn't all make the same call.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Personality {
+    pub aggression: f32,
+    pub caution: f32,
+    pub greed: f32,
+    pub recklessness: f32,
+    pub patience: f32,
+    /// How much target choice favours the weakest duck (1) over the nearest (0)
+    pub bullying: f32,
+}
+
+impl Personality {
+    pub fn roll(rng: &mut impl Rng) -> Self {
+        Self {
+            aggression: rng.gen_range(0.6..1.4),
+            caution: rng.gen_range(0.6..1.4),
+            greed: rng.gen_range(0.4..1.2),
+            recklessness: rng.gen_range(0.4..1.2),
+            patience: rng.gen_range(0.6..1.4),
+            bullying: rng.gen_range(0.3..1.0),
+        }
+    }
+
+    pub fn weight(&self, option: EnemyState) -> f32 {
+        match option {
+            EnemyState::Chase => self.aggression,
+            EnemyState::Retreat => self.caution,
+            EnemyState::GrabPowerUp => self.greed,
+            EnemyState::Ram => self.recklessness,
+            EnemyState::Recover => self.patience,
+            EnemyState::Fallen => 0.0,
+        }
+    }
+}
+
+impl Default for Personality {
+    fn default() -> Self {
+        Self {
+            aggression: 1.0,
+            caution: 1.0,
+            greed: 1.0,
+            recklessness: 1.0,
+            patience: 1.0,
+            bullying: 0.5,
+        }
+    }
+}
+
+/// The scores behind an enemy's last decision, kept for debugging.
+#[derive(Component, Debug, Clone, Default)]
+pub struct AiDecision {
+    /// Weighted score of every option, in `EnemyState::OPTIONS` order
+    pub scores: Vec<(EnemyState, f32)>,
+    pub target: Option<Entity>,
+}
+
 impl Enemy {
-    pub fn new() -> Self {
+    pub fn new(tuning: &EnemyTuning) -> Self {
         Self {
-            state: EnemyState::Patrol,
+            state: EnemyState::Recover,
             target_position: None,
-            state_timer: Timer::from_seconds(1.5, TimerMode::Repeating), // Faster state transitions
+            state_timer: Timer::from_seconds(tuning.think_interval, TimerMode::Repeating),
             is_fallen: false,
-            respawn_timer: Timer::from_seconds(2.0, TimerMode::Once), // Faster respawn
-            health: 100.0,
+            respawn_timer: Timer::from_seconds(tuning.respawn_delay, TimerMode::Once),
             target: None,
-            target_timer: Timer::from_seconds(1.0, TimerMode::Repeating), // More frequent targeting // Initialize with a default value
+            target_timer: Timer::from_seconds(tuning.target_interval, TimerMode::Repeating),
         }
     }
 
-    pub fn get_random_platform_position() -> Vec3 {
-        let mut rng = rand::thread_rng();
-        let x = rng.gen_range(-8.0..8.0);
-        let z = rng.gen_range(-8.0..8.0);
-        Vec3::new(x, PLATFORM_HEIGHT + 2.0, z)
-    }
-
-    pub fn is_weak(&self) -> bool {
-        self.health <= WEAK_THRESHOLD
+    /// A random spot well clear of the edge of `arena`.
+    pub fn get_random_platform_position(rng: &mut impl Rng, arena: &Arena) -> Vec3 {
+        let spot = arena.random_point(rng, arena.depth() * RANDOM_SPOT_MARGIN);
+        arena.spawn_position(spot)
     }
 }
\ No newline at end of file
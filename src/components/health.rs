@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+use crate::resources::HealthTuning;
+
+/// Fraction of max health at or below which a duck counts as weak.
+pub const WEAK_THRESHOLD: f32 = 0.3;
+
+/// Stamina of a duck. Hits drain it, it recovers once the duck has gone a
+/// while without being hit, and the lower it is the further hits knock the
+/// duck back.
+#[derive(Component, Debug)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+    /// Recovery waits for this to run out after every hit
+    pub recovery_timer: Timer,
+}
+
+impl Health {
+    pub fn new(max: f32, tuning: &HealthTuning) -> Self {
+        let mut recovery_timer = Timer::from_seconds(tuning.recovery_delay, TimerMode::Once);
+        recovery_timer.tick(recovery_timer.duration());
+        Self {
+            current: max,
+            max,
+            recovery_timer,
+        }
+    }
+
+    /// Remaining health from 0 (spent) to 1 (full).
+    pub fn fraction(&self) -> f32 {
+        if self.max > 0.0 {
+            (self.current / self.max).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    pub fn is_weak(&self) -> bool {
+        self.fraction() <= WEAK_THRESHOLD
+    }
+
+    pub fn take_damage(&mut self, amount: f32) {
+        self.current = (self.current - amount).max(0.0);
+        self.recovery_timer.reset();
+    }
+
+    /// Back to full, e.g. after a respawn.
+    pub fn restore(&mut self) {
+        self.current = self.max;
+    }
+}
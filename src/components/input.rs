@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Per-player input sampled every frame and consumed by the fixed-timestep
+/// gameplay systems. Edge flags stay latched until a gameplay tick has seen
+/// them, so presses between ticks are never lost or applied twice.
+#[derive(Component, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlayerInput {
+    /// Movement on the XZ plane: `x` is right, `y` is forward (-Z).
+    pub movement: Vec2,
+    pub boost_held: bool,
+    pub boost_pressed: bool,
+    pub boost_released: bool,
+}
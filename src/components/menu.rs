@@ -1,11 +1,35 @@
 use bevy::prelude::*;
 
+use crate::resources::PlayerAction;
+
 #[derive(Component, Debug, Clone, PartialEq)]
 pub struct MainMenu;
 
 #[derive(Component, Debug, Clone, PartialEq)]
 pub struct SettingsMenu;
 
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct HighScoreMenu;
+
+/// Column of the high-score table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HighScoreColumn {
+    Date,
+    Mode,
+    Seed,
+    Score,
+    Placement,
+    Duration,
+}
+
+/// Header button that sorts the high-score table by its column.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HighScoreSortButton(pub HighScoreColumn);
+
+/// Container the high-score rows are rebuilt into when the sort changes.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct HighScoreTable;
+
 #[derive(Component, Debug, Clone, PartialEq)]
 pub struct MenuButton {
     pub action: MenuButtonAction,
@@ -14,9 +38,41 @@ pub struct MenuButton {
 #[derive(Debug, Clone, PartialEq)]
 pub enum MenuButtonAction {
     Play,
+    /// Cycles the number of local human players
+    Players,
+    /// Cycles the AI difficulty preset
+    Difficulty,
+    /// Cycles the arena the match is played on
+    Arena,
+    /// Toggles the shrinking platform
+    SuddenDeath,
+    /// Toggles between a fixed and a tilting platform
+    Tilting,
+    HighScores,
     Settings,
     Quit,
     Back,
     Secret,
     TestWin,
 }
+
+/// A setting the settings screen lets the player change.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsControl {
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    WindowMode,
+    Vsync,
+    Shadows,
+    Difficulty,
+    Binding(PlayerAction),
+}
+
+/// Text showing the current value of a settings control.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettingsValueText(pub SettingsControl);
+
+/// Filled part of a volume slider.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettingsSliderFill(pub SettingsControl);
@@ -1,36 +1,81 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, window::WindowMode};
+use serde::{Deserialize, Serialize};
+
+use crate::resources::{ArenaChoice, Difficulty, InputBindings};
 
 pub mod menu;
 pub mod player;
 pub mod ui;
 pub mod enemy;
 pub mod boost;
-pub mod score;
+pub mod contestant;
 pub mod powerup;
-
-pub mod duck_score;
-pub use duck_score::*;
+pub mod input;
+pub mod health;
+pub mod combat;
 
 pub mod duck_model;
 pub use duck_model::*;
 
+pub mod platform;
+pub use platform::*;
+
 pub use menu::*;
 pub use player::*;
 pub use ui::*;
 pub use enemy::*;
 pub use boost::*;
-pub use score::*;
+pub use contestant::*;
 pub use powerup::*;
+pub use input::*;
+pub use health::*;
+pub use combat::*;
 
-#[derive(Resource)]
+/// User settings, persisted to the config directory by the settings plugin.
+#[derive(Resource, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GameSettings {
+    #[serde(skip)]
     pub paused: bool,
+    /// Fixed match seed; a random seed is picked per match when `None`.
+    /// Session-only, set from the command line.
+    #[serde(skip)]
+    pub seed: Option<u64>,
+    /// Human ducks in a match, `1..=MAX_LOCAL_PLAYERS`
+    pub player_count: usize,
+    /// Volumes are `0.0..=1.0`; music and sound effects are scaled by the master volume
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub window_mode: WindowMode,
+    pub vsync: bool,
+    pub shadows: bool,
+    pub difficulty: Difficulty,
+    pub arena: ArenaChoice,
+    /// Shrink the platform in stages to break up long matches
+    pub sudden_death: bool,
+    /// Balance the platform on a pivot instead of fixing it in place
+    pub tilting: bool,
+    pub bindings: InputBindings,
 }
 
 impl Default for GameSettings {
     fn default() -> Self {
         Self {
             paused: false,
+            seed: None,
+            player_count: 1,
+            master_volume: 0.8,
+            music_volume: 0.6,
+            sfx_volume: 1.0,
+            window_mode: WindowMode::Windowed,
+            vsync: true,
+            shadows: true,
+            difficulty: Difficulty::default(),
+            arena: ArenaChoice::default(),
+            sudden_death: false,
+            tilting: false,
+            bindings: InputBindings::default(),
         }
     }
 }
@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+
+use crate::resources::PieceShape;
+
+/// The floating platform the ducks fight on. Its pieces are children, laid
+/// out from the `Arena` in play.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Platform;
+
+/// Balance state of a platform that tilts on its pivot. Angles are in
+/// radians about the X and Z axes.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct PlatformTilt {
+    pub angle: Vec2,
+    pub angular_velocity: Vec2,
+}
+
+/// One solid piece of the platform.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct PlatformPiece {
+    pub shape: PieceShape,
+    pub thickness: f32,
+}
+
+/// Cosmetic rim around a platform piece.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct PlatformEdge {
+    pub shape: PieceShape,
+    pub thickness: f32,
+}
@@ -1,19 +1,29 @@
 use bevy::prelude::*;
 
+/// Most human ducks a local match supports.
+pub const MAX_LOCAL_PLAYERS: usize = 4;
+
+/// Body color of each local player's duck, by player index.
+pub const PLAYER_COLORS: [Color; MAX_LOCAL_PLAYERS] = [
+    Color::rgb(0.2, 0.7, 0.2),
+    Color::rgb(0.2, 0.4, 0.9),
+    Color::rgb(0.9, 0.8, 0.2),
+    Color::rgb(0.6, 0.3, 0.8),
+];
+
 #[derive(Component)]
 pub struct Player {
-    /// Speed used for player initialization and potential future movement mechanics
-    #[allow(dead_code)]
-    pub speed: f32,
+    /// Local player slot, `0..MAX_LOCAL_PLAYERS`; picks the HUD row and input device
+    pub index: usize,
     pub base_scale: Vec3,
     pub current_scale: Vec3,
 }
 
 impl Player {
-    pub fn new(speed: f32) -> Self {
+    pub fn new(index: usize) -> Self {
         let base_scale = Vec3::new(1.0, 1.0, 1.0);
         Self {
-            speed,
+            index,
             base_scale,
             current_scale: base_scale,
         }
@@ -1,5 +1,5 @@
 // Removed unused import
-use bevy::ecs::component::Component;
+use bevy::ecs::{component::Component, entity::Entity};
 
 #[derive(Component)]
 pub struct ScoreText;
@@ -7,12 +7,49 @@ pub struct ScoreText;
 #[derive(Component)]
 pub struct GameHud;
 
+/// Fill of one player's boost bar in the HUD.
 #[derive(Component)]
-pub struct BoostIndicator;
+pub struct BoostIndicator {
+    pub player: usize,
+}
+
+/// One player's own score line in the multiplayer HUD.
+#[derive(Component)]
+pub struct PlayerScoreText {
+    pub player: usize,
+}
 
 #[derive(Component)]
 pub struct BoostText;
 
+/// Wave counter in the HUD.
+#[derive(Component)]
+pub struct WaveText;
+
+/// Sudden death countdown and stage announcements.
+#[derive(Component)]
+pub struct SuddenDeathText;
+
+/// Recent knockouts, newest last.
+#[derive(Component)]
+pub struct KnockoutFeedText;
+
+/// HUD column holding one health bar per duck.
+#[derive(Component)]
+pub struct HealthBarList;
+
+/// One duck's row in the HUD health list.
+#[derive(Component)]
+pub struct HealthBarRow {
+    pub owner: Entity,
+}
+
+/// Fill of one duck's health bar.
+#[derive(Component)]
+pub struct HealthBarFill {
+    pub owner: Entity,
+}
+
 #[derive(Component)]
 pub struct GameOverScreen;
 
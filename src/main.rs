@@ -5,24 +5,84 @@ mod styles;
 mod states;
 mod resources;
 
-use bevy::{
-    prelude::*,
-    window::WindowMode,
-};
-use plugins::{MenuPlugin, GamePlugin, SettingsPlugin};
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use plugins::{MenuPlugin, GamePlugin, SettingsPlugin, headless_app, present_mode};
+use components::GameSettings;
+use resources::{load_settings, last_replay_path, MatchHistory, Replay, ReplayPlayback, ReplayRecorder};
 use states::GameState;
 
 fn main() {
-    App::new()
+    let replay = replay_arg();
+    let record_path = arg_value("--record").map(PathBuf::from);
+
+    if std::env::args().any(|arg| arg == "--headless") {
+        // Headless runs ignore the user's saved settings so CI results don't depend on them
+        let mut settings = GameSettings {
+            seed: seed_arg(),
+            ..default()
+        };
+        if let Some(replay) = &replay {
+            replay.apply_to(&mut settings);
+        }
+        headless_app(settings, ReplayRecorder::new(record_path), replay).run();
+        return;
+    }
+
+    let mut settings = load_settings();
+    settings.seed = seed_arg();
+    if let Some(replay) = &replay {
+        replay.apply_to(&mut settings);
+    }
+
+    let window = Window {
+        title: "Bevy Demo - Spinning Cube".into(),
+        mode: settings.window_mode,
+        present_mode: present_mode(settings.vsync),
+        ..default()
+    };
+
+    let mut app = App::new();
+    if let Some(replay) = replay {
+        app.insert_resource(ReplayPlayback::new(replay));
+    }
+    app
         .add_state::<GameState>()
+        .insert_resource(ReplayRecorder::new(record_path.or_else(last_replay_path)))
+        .insert_resource(MatchHistory::load(MatchHistory::default_path()))
+        .insert_resource(settings)
         .add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                title: "Bevy Demo - Spinning Cube".into(),
-                mode: WindowMode::Windowed,
-                ..default()
-            }),
+            primary_window: Some(window),
             ..default()
         }))
         .add_plugins((MenuPlugin, GamePlugin, SettingsPlugin))
         .run();
 }
+
+/// Reads the value following `name` on the command line, e.g. `--seed 42`.
+fn arg_value(name: &str) -> Option<String> {
+    let args: Vec<String> = std::env::args().collect();
+    args.iter()
+        .position(|arg| arg == name)
+        .and_then(|index| args.get(index + 1))
+        .cloned()
+}
+
+/// Reads `--seed <n>` from the command line.
+fn seed_arg() -> Option<u64> {
+    arg_value("--seed").and_then(|value| value.parse().ok())
+}
+
+/// Loads the file named by `--replay <path>`. Exits if it can't be read, since
+/// silently playing a different match would defeat the point.
+fn replay_arg() -> Option<Replay> {
+    let path = PathBuf::from(arg_value("--replay")?);
+    match Replay::load(&path) {
+        Ok(replay) => Some(replay),
+        Err(err) => {
+            eprintln!("Failed to load replay {}: {}", path.display(), err);
+            std::process::exit(1);
+        }
+    }
+}
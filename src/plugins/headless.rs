@@ -0,0 +1,166 @@
+use std::time::Duration;
+
+use bevy::{
+    app::AppExit,
+    asset::AssetPlugin,
+    input::InputPlugin,
+    log::LogPlugin,
+    prelude::*,
+    time::TimeUpdateStrategy,
+};
+
+use crate::components::{Contestant, GameSettings, Player};
+use crate::plugins::SimulationPlugin;
+use crate::resources::{arenas_ready, tuning_ready, Replay, ReplayPlayback, ReplayRecorder, StandingEntry};
+use crate::states::GameState;
+
+/// Simulated frame length for headless runs.
+const HEADLESS_FRAME_TIME: f32 = 1.0 / 60.0;
+
+/// Drives a single match without a window: enters `InGame` once tuning has loaded,
+/// advances time by a fixed step every update and exits once the match ends
+/// or `max_match_seconds` of game time have passed.
+pub struct HeadlessPlugin {
+    pub max_match_seconds: f32,
+}
+
+impl Default for HeadlessPlugin {
+    fn default() -> Self {
+        Self {
+            max_match_seconds: 300.0,
+        }
+    }
+}
+
+#[derive(Resource)]
+struct MatchTimeLimit(Timer);
+
+/// An app that plays one match with no window or GPU and exits when it is
+/// decided, playing `replay` back instead of live input when given.
+pub fn headless_app(settings: GameSettings, recorder: ReplayRecorder, replay: Option<Replay>) -> App {
+    let mut app = App::new();
+    if let Some(replay) = replay {
+        app.insert_resource(ReplayPlayback::new(replay));
+    }
+    app
+        .add_state::<GameState>()
+        .insert_resource(settings)
+        .insert_resource(recorder)
+        .add_plugins((
+            MinimalPlugins,
+            LogPlugin::default(),
+            TransformPlugin,
+            HierarchyPlugin,
+            InputPlugin,
+            AssetPlugin::default(),
+        ))
+        .add_plugins((SimulationPlugin, HeadlessPlugin::default()));
+    app
+}
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+                HEADLESS_FRAME_TIME,
+            )))
+            .insert_resource(MatchTimeLimit(Timer::from_seconds(
+                self.max_match_seconds,
+                TimerMode::Once,
+            )))
+            .add_systems(Update, start_match
+                .run_if(in_state(GameState::MainMenu))
+                .run_if(tuning_ready)
+                .run_if(arenas_ready))
+            .add_systems(Update, enforce_time_limit.run_if(in_state(GameState::InGame)))
+            .add_systems(OnEnter(GameState::GameOver), report_match_end)
+            .add_systems(OnEnter(GameState::WinScreen), report_match_end);
+    }
+}
+
+fn start_match(mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(GameState::InGame);
+}
+
+/// Ends an undecided match. No result is recorded for it, so the standings
+/// are logged here instead.
+fn enforce_time_limit(
+    time: Res<Time>,
+    mut limit: ResMut<MatchTimeLimit>,
+    contestant_query: Query<(&Contestant, Option<&Player>)>,
+    mut app_exit_events: EventWriter<AppExit>,
+) {
+    if limit.0.tick(time.delta()).just_finished() {
+        info!("Match hit the time limit");
+        for (contestant, player) in contestant_query.iter() {
+            info!("{}", StandingEntry::new(contestant, player.is_some()));
+        }
+        app_exit_events.send(AppExit);
+    }
+}
+
+/// The final standings were already logged when the match result was recorded.
+fn report_match_end(state: Res<State<GameState>>, mut app_exit_events: EventWriter<AppExit>) {
+    info!("Match ended: {:?}", state.get());
+    app_exit_events.send(AppExit);
+}
+
+/// Updates `app` until the asset files have loaded and its match has started.
+#[cfg(test)]
+pub(crate) fn start_headless_match(app: &mut App) {
+    for _ in 0..2000 {
+        app.update();
+        if *app.world.resource::<State<GameState>>().get() == GameState::InGame {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    panic!("the headless match never started");
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_rapier3d::prelude::RigidBody;
+
+    use super::*;
+    use crate::components::{Platform, Player};
+    use crate::resources::{Arena, MatchClock};
+
+    #[test]
+    fn headless_match_runs_fixed_ticks_without_a_window() {
+        let settings = GameSettings {
+            seed: Some(1),
+            ..default()
+        };
+        let mut app = headless_app(settings, ReplayRecorder::new(None), None);
+        start_headless_match(&mut app);
+
+        for _ in 0..120 {
+            app.update();
+        }
+
+        assert_eq!(app.world.query::<&Window>().iter(&app.world).count(), 0);
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::InGame);
+        assert!(app.world.resource::<MatchClock>().0.as_secs_f32() > 1.5);
+
+        let platforms = app
+            .world
+            .query_filtered::<&RigidBody, With<Platform>>()
+            .iter(&app.world)
+            .count();
+        assert_eq!(platforms, 1);
+
+        // The player dropped in above the platform and physics landed it there
+        let arena = app.world.resource::<Arena>().clone();
+        let spawn = arena.player_spawn(0);
+        let players = app
+            .world
+            .query_filtered::<&Transform, With<Player>>()
+            .iter(&app.world)
+            .map(|transform| transform.translation)
+            .collect::<Vec<_>>();
+        assert_eq!(players.len(), 1);
+        assert!(players[0].y < spawn.y, "player never fell: {}", players[0]);
+        assert!(arena.is_standing(players[0]), "player fell through: {}", players[0]);
+    }
+}
@@ -1,7 +1,10 @@
 use bevy::prelude::*;
 
 use crate::{
-    systems::{spawn_main_menu, handle_menu_buttons, cleanup_menu, spawn_settings_menu},
+    systems::{
+        spawn_main_menu, handle_menu_buttons, cleanup_menu, spawn_settings_menu,
+        spawn_high_score_screen, handle_high_score_input, refresh_high_score_table, HighScoreSort,
+    },
     states::GameState,
     resources::{WinterBackgroundPlugin, spawn_winter_background, cleanup_winter_background},
 };
@@ -11,11 +14,21 @@ pub struct MenuPlugin;
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
         app
+            .init_resource::<HighScoreSort>()
             .add_plugins(WinterBackgroundPlugin)
             .add_systems(OnEnter(GameState::MainMenu), (spawn_main_menu, spawn_winter_background))
             .add_systems(OnEnter(GameState::Settings), (spawn_settings_menu, spawn_winter_background))
-            .add_systems(Update, handle_menu_buttons.run_if(in_state(GameState::MainMenu).or_else(in_state(GameState::Settings))))
+            .add_systems(OnEnter(GameState::HighScores), (spawn_high_score_screen, spawn_winter_background))
+            .add_systems(Update, handle_menu_buttons.run_if(
+                in_state(GameState::MainMenu)
+                    .or_else(in_state(GameState::Settings))
+                    .or_else(in_state(GameState::HighScores))
+            ))
+            .add_systems(Update, (handle_high_score_input, refresh_high_score_table)
+                .chain()
+                .run_if(in_state(GameState::HighScores)))
             .add_systems(OnExit(GameState::MainMenu), (cleanup_menu, cleanup_winter_background))
-            .add_systems(OnExit(GameState::Settings), cleanup_menu);
+            .add_systems(OnExit(GameState::Settings), (cleanup_menu, cleanup_winter_background))
+            .add_systems(OnExit(GameState::HighScores), (cleanup_menu, cleanup_winter_background));
     }
 }
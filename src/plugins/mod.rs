@@ -1,7 +1,15 @@
 mod menu;
 mod game;
 mod settings;
+mod simulation;
+mod presentation;
+mod headless;
 
 pub use menu::MenuPlugin;
 pub use game::GamePlugin;
-pub use settings::SettingsPlugin;
+pub use settings::{SettingsPlugin, present_mode};
+pub use simulation::{SimulationPlugin, run_if_resuming_from_pause};
+pub use presentation::PresentationPlugin;
+pub use headless::headless_app;
+#[cfg(test)]
+pub(crate) use headless::start_headless_match;
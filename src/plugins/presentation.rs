@@ -0,0 +1,94 @@
+use bevy::{prelude::*, ui::UiSystem};
+use bevy_rapier3d::prelude::*;
+
+use crate::states::GameState;
+use crate::resources::GameAudioPlugin;
+use crate::plugins::simulation::run_if_resuming_from_pause;
+use crate::systems::{
+    setup_scene,
+    attach_platform_visuals,
+    attach_duck_visuals,
+    attach_powerup_coin_visuals,
+    update_boost_indicator,
+    draw_boost_telegraph,
+    draw_impact_rings,
+    spawn_hud,
+    spawn_game_over_screen,
+    handle_game_over_input,
+    cleanup_game_over,
+    toggle_pause,
+    spawn_pause_menu,
+    handle_pause_input,
+    cleanup_pause_menu,
+    spawn_win_screen,
+    cleanup_win_screen,
+    handle_win_screen_input,
+    update_score_text,
+    update_player_score_text,
+    update_knockout_feed,
+    update_wave_text,
+    draw_spawn_telegraphs,
+    update_sudden_death_text,
+    draw_shrink_telegraph,
+    sync_health_bars,
+    update_health_bars,
+    update_camera_position,
+    update_action_state,
+    navigate_menu_focus,
+    highlight_menu_focus,
+    MenuFocus,
+    toggle_ai_debug_overlay,
+    draw_ai_debug_overlay,
+    AiDebugOverlay,
+};
+
+/// Everything that draws the simulation: lights, camera, meshes, HUD and the
+/// in-game screens, plus its sound.
+pub struct PresentationPlugin;
+
+impl Plugin for PresentationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_plugins((RapierDebugRenderPlugin::default(), GameAudioPlugin))
+            .init_resource::<MenuFocus>()
+            .init_resource::<AiDebugOverlay>()
+            .add_systems(
+                PreUpdate,
+                navigate_menu_focus.after(UiSystem::Focus).after(update_action_state),
+            )
+            .add_systems(PostUpdate, highlight_menu_focus)
+            .add_systems(
+                OnEnter(GameState::InGame),
+                (setup_scene, spawn_hud)
+                    .run_if(not(run_if_resuming_from_pause))
+            )
+            .add_systems(Update, (
+                attach_platform_visuals,
+                attach_duck_visuals,
+                attach_powerup_coin_visuals,
+                update_boost_indicator,
+                draw_boost_telegraph,
+                draw_impact_rings,
+                update_score_text,
+                update_player_score_text,
+                update_knockout_feed,
+                update_wave_text,
+                draw_spawn_telegraphs,
+                update_sudden_death_text,
+                draw_shrink_telegraph,
+                (sync_health_bars, update_health_bars).chain(),
+                update_camera_position,
+                toggle_pause,
+                (toggle_ai_debug_overlay, draw_ai_debug_overlay).chain(),
+            ).run_if(in_state(GameState::InGame)))
+            .add_systems(OnEnter(GameState::Paused), spawn_pause_menu)
+            .add_systems(Update, handle_pause_input.run_if(in_state(GameState::Paused)))
+            .add_systems(OnExit(GameState::Paused), cleanup_pause_menu)
+            .add_systems(OnEnter(GameState::GameOver), spawn_game_over_screen)
+            .add_systems(Update, handle_game_over_input.run_if(in_state(GameState::GameOver)))
+            .add_systems(OnExit(GameState::GameOver), cleanup_game_over)
+            .add_systems(OnEnter(GameState::WinScreen), spawn_win_screen)
+            .add_systems(Update, handle_win_screen_input.run_if(in_state(GameState::WinScreen)))
+            .add_systems(OnExit(GameState::WinScreen), cleanup_win_screen);
+    }
+}
@@ -1,66 +1,66 @@
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    window::{PresentMode, PrimaryWindow},
+};
 
 use crate::states::GameState;
 use crate::components::GameSettings;
+use crate::resources::{ReplayPlayback, persist_settings};
+use crate::systems::{
+    capture_rebind_key,
+    handle_settings_back,
+    handle_settings_controls,
+    update_settings_values,
+    PendingRebind,
+};
 
+/// The settings screen's controls, and applying and saving `GameSettings`
+/// whenever they change.
 pub struct SettingsPlugin;
 
 impl Plugin for SettingsPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(OnEnter(GameState::Settings), setup_settings)
-            .add_systems(Update, handle_settings.run_if(in_state(GameState::Settings)))
-            .add_systems(OnExit(GameState::Settings), cleanup_settings);
+            .init_resource::<PendingRebind>()
+            .add_systems(Update, (
+                handle_settings_back,
+                capture_rebind_key,
+                handle_settings_controls,
+                update_settings_values,
+            ).chain().run_if(in_state(GameState::Settings)))
+            .add_systems(OnExit(GameState::Settings), cancel_rebind)
+            .add_systems(Update, apply_window_settings.run_if(resource_changed::<GameSettings>()))
+            // A replay's seed and player count are not the user's own settings
+            .add_systems(Last, persist_settings
+                .run_if(resource_changed::<GameSettings>())
+                .run_if(not(resource_exists::<ReplayPlayback>())));
     }
 }
 
-#[derive(Component)]
-struct SettingsMenu;
-
-fn setup_settings(mut commands: Commands) {
-    // Camera
-    commands.spawn(Camera2dBundle::default());
-
-    // Settings UI
-    commands.spawn((
-        NodeBundle {
-            style: Style {
-                width: Val::Percent(100.0),
-                height: Val::Percent(100.0),
-                flex_direction: FlexDirection::Column,
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                ..default()
-            },
-            background_color: Color::NONE.into(),
-            ..default()
-        },
-        SettingsMenu,
-    ));
-}
-
-pub fn handle_settings(
-    keys: Res<Input<KeyCode>>,
-    _settings: ResMut<GameSettings>,
-    mut next_state: ResMut<NextState<GameState>>,
-) {
-    if keys.just_pressed(KeyCode::Escape) {
-        next_state.set(GameState::MainMenu);
+/// Vsync on or off as a window present mode.
+pub fn present_mode(vsync: bool) -> PresentMode {
+    if vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
     }
 }
 
-fn cleanup_settings(
-    mut commands: Commands,
-    settings_query: Query<Entity, With<SettingsMenu>>,
-    camera_query: Query<Entity, With<Camera>>,
+fn apply_window_settings(
+    settings: Res<GameSettings>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
 ) {
-    // Cleanup settings UI
-    for entity in settings_query.iter() {
-        commands.entity(entity).despawn_recursive();
+    for mut window in window_query.iter_mut() {
+        if window.mode != settings.window_mode {
+            window.mode = settings.window_mode;
+        }
+        let present_mode = present_mode(settings.vsync);
+        if window.present_mode != present_mode {
+            window.present_mode = present_mode;
+        }
     }
+}
 
-    // Cleanup camera
-    for entity in camera_query.iter() {
-        commands.entity(entity).despawn_recursive();
-    }
+fn cancel_rebind(mut pending: ResMut<PendingRebind>) {
+    pending.0 = None;
 }
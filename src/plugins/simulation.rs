@@ -0,0 +1,193 @@
+use bevy::{input::InputSystem, prelude::*};
+use bevy_rapier3d::prelude::*;
+
+use crate::states::GameState;
+use crate::components::{DuckKnockedOut, DuckPushed, GameSettings, PauseState};
+use crate::resources::{
+    ActionState, GameRng, TuningPlugin, ArenaPlugin, apply_selected_arena, ReplayPlugin, ReplayPlayback, seed_match_rng,
+    MatchHistory, MatchClock, MatchResult, reset_match_clock, tick_match_clock,
+    record_match_result, match_decided, SoundEffect,
+};
+use crate::systems::{
+    player_movement,
+    check_fall,
+    enemy_behavior,
+    enemy_decide,
+    handle_enemy_falls,
+    reset_wave_director,
+    run_wave_director,
+    WaveDirector,
+    reset_sudden_death,
+    run_sudden_death,
+    SuddenDeath,
+    spawn_platform,
+    tilt_platform,
+    handle_boost,
+    handle_ai_boost,
+    face_movement,
+    spawn_player,
+    apply_powerup_effects,
+    spawn_random_powerup_coin,
+    collect_powerup_coin,
+    resolve_duck_hits,
+    tick_hit_stun,
+    track_last_hitter,
+    award_knockouts,
+    recover_health,
+    remove_expired_powerup_coins,
+    check_win_condition,
+    PowerUpSpawnTimer,
+    reset_powerup_spawn_timer,
+    update_action_state,
+    read_player_input,
+    clear_player_input_edges,
+    GameMechanicsSet,
+};
+
+/// Gameplay and physics tick rate.
+const FIXED_TIMESTEP_HZ: f64 = 60.0;
+
+/// Gameplay rules and physics. Needs `AssetPlugin` for the tuning and arena
+/// files, but no window or renderer, so it can run under `MinimalPlugins`
+/// for CI and tooling.
+pub struct SimulationPlugin;
+
+/// Despawns everything the match spawned. Playing sounds are left to finish
+/// so the last fall isn't cut off by the game-over screen.
+fn cleanup_game(
+    mut commands: Commands,
+    query: Query<Entity, (Without<Camera>, Without<Window>, Without<Handle<AudioSource>>)>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn conditional_cleanup_game(
+    commands: Commands,
+    query: Query<Entity, (Without<Camera>, Without<Window>, Without<Handle<AudioSource>>)>,
+    pause_state: Res<PauseState>,
+    next_state: Res<NextState<GameState>>,
+) {
+    // Only clean up if we're not transitioning to the pause state
+    if !pause_state.transitioning_to_pause {
+        // Check if we're transitioning to a state other than Paused
+        if let Some(state) = next_state.0.as_ref() {
+            if *state != GameState::Paused {
+                cleanup_game(commands, query);
+            }
+        } else {
+            // If no next state is set, clean up anyway
+            cleanup_game(commands, query);
+        }
+    }
+}
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<GameSettings>()
+            .init_resource::<PauseState>()
+            .init_resource::<WaveDirector>()
+            .init_resource::<SuddenDeath>()
+            .init_resource::<GameRng>()
+            .init_resource::<ActionState>()
+            .init_resource::<PowerUpSpawnTimer>()
+            .init_resource::<MatchHistory>()
+            .init_resource::<MatchClock>()
+            .init_resource::<MatchResult>()
+            .add_event::<SoundEffect>()
+            .add_event::<DuckPushed>()
+            .add_event::<DuckKnockedOut>()
+            .insert_resource(Time::<Fixed>::from_hz(FIXED_TIMESTEP_HZ))
+            .add_plugins((TuningPlugin, ArenaPlugin, ReplayPlugin))
+            .add_plugins(RapierPhysicsPlugin::<NoUserData>::default().in_fixed_schedule())
+            .add_systems(Startup, configure_physics_timestep)
+            // Rapier steps in FixedUpdate whatever the state; holding it while paused keeps
+            // physics in step with the ticks replays record
+            .add_systems(OnEnter(GameState::Paused), pause_physics)
+            .add_systems(OnExit(GameState::Paused), resume_physics)
+            .configure_sets(FixedUpdate, (
+                (GameMechanicsSet::Movement, GameMechanicsSet::EnemyBehavior)
+                    .before(PhysicsSet::SyncBackend),
+                (GameMechanicsSet::Collisions, GameMechanicsSet::Scoring)
+                    .chain()
+                    .after(PhysicsSet::Writeback),
+            ))
+            // Add a system set that runs when entering InGame from a state other than Paused
+            .add_systems(
+                OnEnter(GameState::InGame),
+                (
+                    (
+                        seed_match_rng,
+                        reset_powerup_spawn_timer,
+                        reset_match_clock,
+                        reset_wave_director,
+                        reset_sudden_death,
+                        apply_selected_arena,
+                    ),
+                    (spawn_platform, spawn_player),
+                ).chain()
+                    .run_if(not(run_if_resuming_from_pause))
+            )
+            .add_systems(PreUpdate, update_action_state.after(InputSystem))
+            // Live input is ignored while a replay drives the players
+            .add_systems(Update, read_player_input
+                .run_if(in_state(GameState::InGame))
+                .run_if(not(resource_exists::<ReplayPlayback>())))
+            .add_systems(FixedUpdate, (
+                (tick_hit_stun, handle_boost, handle_ai_boost, player_movement, apply_powerup_effects)
+                    .chain()
+                    .in_set(GameMechanicsSet::Movement),
+                clear_player_input_edges.after(GameMechanicsSet::Movement),
+                (enemy_decide, enemy_behavior).chain().in_set(GameMechanicsSet::EnemyBehavior),
+                run_sudden_death.before(PhysicsSet::SyncBackend),
+                tilt_platform
+                    .after(GameMechanicsSet::Movement)
+                    .after(GameMechanicsSet::EnemyBehavior)
+                    .before(PhysicsSet::SyncBackend),
+                face_movement
+                    .after(GameMechanicsSet::Movement)
+                    .after(GameMechanicsSet::EnemyBehavior)
+                    .before(PhysicsSet::SyncBackend),
+                (collect_powerup_coin, (resolve_duck_hits, track_last_hitter).chain())
+                    .in_set(GameMechanicsSet::Collisions),
+                (check_fall, handle_enemy_falls, award_knockouts, run_wave_director, check_win_condition)
+                    .chain()
+                    .in_set(GameMechanicsSet::Scoring),
+                spawn_random_powerup_coin,
+                remove_expired_powerup_coins,
+                recover_health,
+                tick_match_clock,
+            ).run_if(in_state(GameState::InGame)))
+            // Standings are read before the match entities are cleaned up
+            .add_systems(OnExit(GameState::InGame), (
+                record_match_result.run_if(match_decided),
+                conditional_cleanup_game,
+            ).chain());
+    }
+}
+
+// Function to check if we're resuming from pause
+pub fn run_if_resuming_from_pause(
+    pause_state: Res<PauseState>,
+) -> bool {
+    // If was_paused is true, we're resuming from pause
+    pause_state.was_paused
+}
+
+fn pause_physics(mut config: ResMut<RapierConfiguration>) {
+    config.physics_pipeline_active = false;
+}
+
+fn resume_physics(mut config: ResMut<RapierConfiguration>) {
+    config.physics_pipeline_active = true;
+}
+
+/// Steps rapier by exactly one fixed tick each time `FixedUpdate` runs.
+fn configure_physics_timestep(mut config: ResMut<RapierConfiguration>) {
+    config.timestep_mode = TimestepMode::Fixed {
+        dt: (1.0 / FIXED_TIMESTEP_HZ) as f32,
+        substeps: 1,
+    };
+}
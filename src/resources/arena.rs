@@ -0,0 +1,478 @@
+use std::f32::consts::{FRAC_PI_6, PI, TAU};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext, LoadState},
+    prelude::*,
+    utils::BoxedFuture,
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::components::GameSettings;
+
+/// Box segments a ring arena is built from.
+const RING_SEGMENTS: usize = 24;
+/// Tries at finding a random spot on the platform before settling for the middle.
+const RANDOM_POINT_TRIES: usize = 32;
+/// Segments a round piece's outline is drawn with.
+const OUTLINE_SEGMENTS: usize = 48;
+/// Smallest scale the arena geometry is computed at, so a fully shrunk
+/// platform doesn't divide by zero.
+const MIN_SCALE: f32 = 0.01;
+
+/// Arenas that ship with the game, each loaded from `assets/arenas/<file>.arena.ron`.
+/// Picked in the main menu and saved with `GameSettings`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum ArenaChoice {
+    #[default]
+    Circle,
+    Square,
+    Hexagon,
+    Ring,
+    Islands,
+}
+
+impl ArenaChoice {
+    pub const ALL: [ArenaChoice; 5] = [
+        ArenaChoice::Circle,
+        ArenaChoice::Square,
+        ArenaChoice::Hexagon,
+        ArenaChoice::Ring,
+        ArenaChoice::Islands,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ArenaChoice::Circle => "Circle",
+            ArenaChoice::Square => "Square",
+            ArenaChoice::Hexagon => "Hexagon",
+            ArenaChoice::Ring => "Ring",
+            ArenaChoice::Islands => "Islands",
+        }
+    }
+
+    pub fn path(self) -> &'static str {
+        match self {
+            ArenaChoice::Circle => "arenas/circle.arena.ron",
+            ArenaChoice::Square => "arenas/square.arena.ron",
+            ArenaChoice::Hexagon => "arenas/hexagon.arena.ron",
+            ArenaChoice::Ring => "arenas/ring.arena.ron",
+            ArenaChoice::Islands => "arenas/islands.arena.ron",
+        }
+    }
+
+    /// The next arena, wrapping from the last back to the first.
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|arena| *arena == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// One island of an `ArenaShape::Islands` arena.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Island {
+    pub center: Vec2,
+    pub radius: f32,
+}
+
+/// Outline of the platform, seen from above and centered on the origin.
+#[derive(Debug, Clone, Deserialize)]
+pub enum ArenaShape {
+    Circle { radius: f32 },
+    Square { half_size: f32 },
+    /// `radius` is measured to the corners
+    Hexagon { radius: f32 },
+    /// A circle with a hole in the middle to fall through
+    Ring { outer_radius: f32, inner_radius: f32 },
+    Islands { islands: Vec<Island> },
+}
+
+/// Solid a platform piece is made of, as spawned by `spawn_platform`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PieceShape {
+    /// Round slab; `sides` turns it into a regular polygon with corners at `radius`
+    Disc { radius: f32, sides: Option<usize> },
+    /// Rectangular slab; `half_extents` are along its local X and Z
+    Slab { half_extents: Vec2 },
+}
+
+/// A piece of the platform, placed relative to the arena's middle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArenaPiece {
+    pub offset: Vec2,
+    /// Rotation about Y
+    pub yaw: f32,
+    pub shape: PieceShape,
+}
+
+impl ArenaPiece {
+    /// Corners of the piece seen from above, in arena coordinates, in order
+    /// around its edge.
+    pub fn outline(&self) -> Vec<Vec2> {
+        let local = match self.shape {
+            PieceShape::Disc { radius, sides } => {
+                let corners = sides.unwrap_or(OUTLINE_SEGMENTS);
+                (0..corners)
+                    .map(|corner| {
+                        let angle = corner as f32 * TAU / corners as f32;
+                        Vec2::new(angle.cos(), angle.sin()) * radius
+                    })
+                    .collect::<Vec<_>>()
+            }
+            PieceShape::Slab { half_extents } => vec![
+                Vec2::new(half_extents.x, half_extents.y),
+                Vec2::new(-half_extents.x, half_extents.y),
+                Vec2::new(-half_extents.x, -half_extents.y),
+                Vec2::new(half_extents.x, -half_extents.y),
+            ],
+        };
+        let rotation = Quat::from_rotation_y(self.yaw);
+        local
+            .into_iter()
+            .map(|corner| self.offset + (rotation * Vec3::new(corner.x, 0.0, corner.y)).xz())
+            .collect()
+    }
+}
+
+/// Everything about the platform the ducks fight on: its outline, how high
+/// it floats, where ducks spawn and how far they fall before they are out.
+/// Loaded from an `.arena.ron` file; the default is the classic circle.
+#[derive(Asset, Resource, TypePath, Debug, Clone, Deserialize)]
+pub struct Arena {
+    pub name: String,
+    pub shape: ArenaShape,
+    /// Height of the middle of the platform slab
+    pub height: f32,
+    pub thickness: f32,
+    /// Ducks falling below this lose a life
+    pub kill_plane: f32,
+    /// Where each local player spawns and respawns
+    pub player_spawns: Vec<Vec2>,
+    /// Where waves can bring enemies in
+    pub enemy_spawns: Vec<Vec2>,
+    /// Size of the platform relative to the file, around the middle. Only
+    /// sudden death changes it; everything below already accounts for it.
+    #[serde(skip, default = "full_scale")]
+    pub scale: f32,
+    /// Tilt of the platform about X and Z, in radians. Only the tilting
+    /// platform changes it.
+    #[serde(skip)]
+    pub tilt: Vec2,
+}
+
+fn full_scale() -> f32 {
+    1.0
+}
+
+impl Default for Arena {
+    fn default() -> Self {
+        Self {
+            name: "Circle".to_string(),
+            shape: ArenaShape::Circle { radius: 15.0 },
+            height: 5.0,
+            thickness: 1.0,
+            kill_plane: -5.0,
+            player_spawns: vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(3.0, 0.0),
+                Vec2::new(-3.0, 0.0),
+                Vec2::new(0.0, 3.0),
+            ],
+            enemy_spawns: vec![
+                Vec2::new(-8.0, -8.0),
+                Vec2::new(-8.0, 8.0),
+                Vec2::new(8.0, -8.0),
+                Vec2::new(8.0, 8.0),
+                Vec2::new(0.0, -8.0),
+                Vec2::new(0.0, 8.0),
+            ],
+            scale: full_scale(),
+            tilt: Vec2::ZERO,
+        }
+    }
+}
+
+impl Arena {
+    /// Rotation of the platform about its pivot in the middle.
+    pub fn tilt_rotation(&self) -> Quat {
+        Quat::from_rotation_x(self.tilt.x) * Quat::from_rotation_z(self.tilt.y)
+    }
+
+    /// Height of the middle of the platform slab under `point`, following the
+    /// tilt. Past the platform the slab is extended no further than its extent.
+    pub fn level_at(&self, point: Vec2) -> f32 {
+        let point = point.clamp_length_max(self.extent());
+        let normal = self.tilt_rotation() * Vec3::Y;
+        self.height - (normal.x * point.x + normal.z * point.y) / normal.y.max(0.1)
+    }
+
+    /// Height of the top of the platform under `point`.
+    pub fn surface_at(&self, point: Vec2) -> f32 {
+        self.level_at(point) + self.thickness / 2.0
+    }
+
+    /// How far `position` is above the middle of the platform slab right
+    /// under it; negative once a duck has dropped past the platform.
+    pub fn height_above_platform(&self, position: Vec3) -> f32 {
+        position.y - self.level_at(position.xz())
+    }
+
+    /// Ducks this high are still on the platform; below it they are falling.
+    pub fn is_standing(&self, position: Vec3) -> bool {
+        self.height_above_platform(position) > -1.0
+    }
+
+    /// Where a duck dropped in at `point` starts, just above the platform.
+    pub fn spawn_position(&self, point: Vec2) -> Vec3 {
+        Vec3::new(point.x, self.level_at(point) + 2.0, point.y)
+    }
+
+    /// Spawn point of local player `index`.
+    pub fn player_spawn(&self, index: usize) -> Vec3 {
+        let point = match self.player_spawns.len() {
+            0 => self.home(Vec2::ZERO),
+            count => self.player_spawns[index % count] * self.scale(),
+        };
+        self.spawn_position(point)
+    }
+
+    /// Spots waves can bring enemies in at, moved in with the platform.
+    pub fn enemy_spawn_points(&self) -> Vec<Vec2> {
+        self.enemy_spawns.iter().map(|point| *point * self.scale()).collect()
+    }
+
+    fn scale(&self) -> f32 {
+        self.scale.max(MIN_SCALE)
+    }
+
+    /// How far `point` is inside the platform edge; negative when it is off the platform.
+    pub fn edge_distance(&self, point: Vec2) -> f32 {
+        self.unscaled_edge_distance(point / self.scale()) * self.scale()
+    }
+
+    fn unscaled_edge_distance(&self, point: Vec2) -> f32 {
+        match &self.shape {
+            ArenaShape::Circle { radius } => radius - point.length(),
+            ArenaShape::Square { half_size } => half_size - point.x.abs().max(point.y.abs()),
+            ArenaShape::Hexagon { radius } => {
+                // Edges face 30 degrees off each corner
+                let apothem = radius * FRAC_PI_6.cos();
+                let reach = (0..6)
+                    .map(|side| {
+                        let angle = FRAC_PI_6 + side as f32 * PI / 3.0;
+                        point.dot(Vec2::new(angle.cos(), angle.sin()))
+                    })
+                    .fold(f32::MIN, f32::max);
+                apothem - reach
+            }
+            ArenaShape::Ring { outer_radius, inner_radius } => {
+                let distance = point.length();
+                (outer_radius - distance).min(distance - inner_radius)
+            }
+            ArenaShape::Islands { islands } => islands
+                .iter()
+                .map(|island| island.radius - point.distance(island.center))
+                .fold(f32::MIN, f32::max),
+        }
+    }
+
+    /// Direction from `point` toward safer ground, away from the nearest edge.
+    pub fn inward(&self, point: Vec2) -> Vec2 {
+        const STEP: f32 = 0.05;
+        let gradient = Vec2::new(
+            self.edge_distance(point + Vec2::X * STEP) - self.edge_distance(point - Vec2::X * STEP),
+            self.edge_distance(point + Vec2::Y * STEP) - self.edge_distance(point - Vec2::Y * STEP),
+        );
+        gradient.try_normalize().unwrap_or_else(|| (self.home(point) - point).normalize_or_zero())
+    }
+
+    /// Safest spot of the part of the platform nearest `point`: the middle,
+    /// the middle of the ring's band, or the middle of the nearest island.
+    pub fn home(&self, point: Vec2) -> Vec2 {
+        self.unscaled_home(point / self.scale()) * self.scale()
+    }
+
+    fn unscaled_home(&self, point: Vec2) -> Vec2 {
+        match &self.shape {
+            ArenaShape::Circle { .. } | ArenaShape::Square { .. } | ArenaShape::Hexagon { .. } => Vec2::ZERO,
+            ArenaShape::Ring { outer_radius, inner_radius } => {
+                point.try_normalize().unwrap_or(Vec2::X) * (outer_radius + inner_radius) / 2.0
+            }
+            ArenaShape::Islands { islands } => islands
+                .iter()
+                .min_by(|a, b| {
+                    (point.distance(a.center) - a.radius).total_cmp(&(point.distance(b.center) - b.radius))
+                })
+                .map_or(Vec2::ZERO, |island| island.center),
+        }
+    }
+
+    /// How deep the platform is from edge to safest spot; the edge distance
+    /// at `home`.
+    pub fn depth(&self) -> f32 {
+        let depth = match &self.shape {
+            ArenaShape::Circle { radius } => *radius,
+            ArenaShape::Square { half_size } => *half_size,
+            ArenaShape::Hexagon { radius } => radius * FRAC_PI_6.cos(),
+            ArenaShape::Ring { outer_radius, inner_radius } => (outer_radius - inner_radius) / 2.0,
+            ArenaShape::Islands { islands } => islands.iter().map(|island| island.radius).fold(0.0, f32::max),
+        };
+        depth.max(0.1) * self.scale()
+    }
+
+    /// Distance from the middle to the furthest point of the platform.
+    pub fn extent(&self) -> f32 {
+        let extent = match &self.shape {
+            ArenaShape::Circle { radius } | ArenaShape::Hexagon { radius } => *radius,
+            ArenaShape::Square { half_size } => half_size * 2.0_f32.sqrt(),
+            ArenaShape::Ring { outer_radius, .. } => *outer_radius,
+            ArenaShape::Islands { islands } => islands
+                .iter()
+                .map(|island| island.center.length() + island.radius)
+                .fold(0.0, f32::max),
+        };
+        extent.max(0.1) * self.scale()
+    }
+
+    /// A random spot at least `margin` inside the edge.
+    pub fn random_point(&self, rng: &mut impl Rng, margin: f32) -> Vec2 {
+        let extent = self.extent();
+        (0..RANDOM_POINT_TRIES)
+            .map(|_| Vec2::new(rng.gen_range(-extent..extent), rng.gen_range(-extent..extent)))
+            .find(|point| self.edge_distance(*point) >= margin)
+            .unwrap_or_else(|| self.home(Vec2::ZERO))
+    }
+
+    /// The solids the platform is built from, at full size; the platform
+    /// entity's scale shrinks them along with `scale`.
+    pub fn pieces(&self) -> Vec<ArenaPiece> {
+        match &self.shape {
+            ArenaShape::Circle { radius } => vec![ArenaPiece {
+                offset: Vec2::ZERO,
+                yaw: 0.0,
+                shape: PieceShape::Disc { radius: *radius, sides: None },
+            }],
+            ArenaShape::Square { half_size } => vec![ArenaPiece {
+                offset: Vec2::ZERO,
+                yaw: 0.0,
+                shape: PieceShape::Slab { half_extents: Vec2::splat(*half_size) },
+            }],
+            ArenaShape::Hexagon { radius } => vec![ArenaPiece {
+                offset: Vec2::ZERO,
+                yaw: 0.0,
+                shape: PieceShape::Disc { radius: *radius, sides: Some(6) },
+            }],
+            ArenaShape::Ring { outer_radius, inner_radius } => {
+                // Segments wide enough to close the gaps along the outer edge
+                let band = (outer_radius - inner_radius).max(0.1);
+                let middle = (outer_radius + inner_radius) / 2.0;
+                let half_length = outer_radius * (PI / RING_SEGMENTS as f32).tan();
+                (0..RING_SEGMENTS)
+                    .map(|segment| {
+                        let angle = segment as f32 * TAU / RING_SEGMENTS as f32;
+                        ArenaPiece {
+                            offset: Vec2::new(angle.cos(), angle.sin()) * middle,
+                            // Local X points out from the middle
+                            yaw: -angle,
+                            shape: PieceShape::Slab { half_extents: Vec2::new(band / 2.0, half_length) },
+                        }
+                    })
+                    .collect()
+            }
+            ArenaShape::Islands { islands } => islands
+                .iter()
+                .map(|island| ArenaPiece {
+                    offset: island.center,
+                    yaw: 0.0,
+                    shape: PieceShape::Disc { radius: island.radius, sides: None },
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ArenaLoader;
+
+impl AssetLoader for ArenaLoader {
+    type Asset = Arena;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Arena, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            Ok(ron::de::from_bytes::<Arena>(&bytes)?)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["arena.ron"]
+    }
+}
+
+/// Handles keeping every shipped arena loaded, so edits are picked up for
+/// the next match.
+#[derive(Resource)]
+pub struct ArenaHandles(pub Vec<(ArenaChoice, Handle<Arena>)>);
+
+impl ArenaHandles {
+    fn get(&self, choice: ArenaChoice) -> Option<&Handle<Arena>> {
+        self.0.iter().find(|(arena, _)| *arena == choice).map(|(_, handle)| handle)
+    }
+}
+
+/// Loads the arena files and keeps the `Arena` resource on the one picked
+/// in `GameSettings`.
+pub struct ArenaPlugin;
+
+impl Plugin for ArenaPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Arena>()
+            .init_asset::<Arena>()
+            .init_asset_loader::<ArenaLoader>()
+            .add_systems(Startup, load_arenas);
+    }
+}
+
+fn load_arenas(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(ArenaHandles(
+        ArenaChoice::ALL
+            .iter()
+            .map(|arena| (*arena, asset_server.load(arena.path())))
+            .collect(),
+    ));
+}
+
+/// Run condition: the arena files have finished loading (or failed to), so
+/// a match started now is played on the arena it was set up with.
+pub fn arenas_ready(asset_server: Res<AssetServer>, handles: Option<Res<ArenaHandles>>) -> bool {
+    handles.map_or(true, |handles| handles.0.iter().all(|(_, handle)| {
+        matches!(
+            asset_server.get_load_state(handle),
+            Some(LoadState::Loaded) | Some(LoadState::Failed) | None
+        )
+    }))
+}
+
+/// Puts the arena picked in the settings in play for the match that is
+/// starting. Falls back to the built-in circle when its file didn't load.
+pub fn apply_selected_arena(
+    settings: Res<GameSettings>,
+    handles: Option<Res<ArenaHandles>>,
+    assets: Res<Assets<Arena>>,
+    mut arena: ResMut<Arena>,
+) {
+    let selected = handles
+        .as_ref()
+        .and_then(|handles| handles.get(settings.arena))
+        .and_then(|handle| assets.get(handle));
+    *arena = selected.cloned().unwrap_or_default();
+    info!("Playing on the {} arena", arena.name);
+}
@@ -0,0 +1,221 @@
+use bevy::{
+    audio::{PlaybackMode, Volume},
+    prelude::*,
+};
+
+use crate::components::GameSettings;
+use crate::states::GameState;
+use crate::systems::MenuFocus;
+
+/// A one-shot sound cue raised by gameplay. The simulation only sends these;
+/// `GameAudioPlugin` plays them when the game has a window.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundEffect {
+    BoostStart,
+    BoostStop,
+    Fall,
+    Hit,
+    CoinPickup,
+    Grow,
+    Shrink,
+    ButtonHover,
+    ButtonPress,
+}
+
+/// Background loops, one per group of game states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MusicTrack {
+    Menu,
+    Game,
+    GameOver,
+    Win,
+}
+
+impl MusicTrack {
+    /// The loop that plays in `state`, if any.
+    pub fn for_state(state: GameState) -> Option<Self> {
+        match state {
+            GameState::MainMenu | GameState::Settings | GameState::HighScores => Some(Self::Menu),
+            GameState::InGame | GameState::Paused => Some(Self::Game),
+            GameState::GameOver => Some(Self::GameOver),
+            GameState::WinScreen => Some(Self::Win),
+            GameState::SecretScene => None,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct AudioAssets {
+    boost_start: Handle<AudioSource>,
+    boost_stop: Handle<AudioSource>,
+    fall: Handle<AudioSource>,
+    hit: Handle<AudioSource>,
+    coin: Handle<AudioSource>,
+    grow: Handle<AudioSource>,
+    shrink: Handle<AudioSource>,
+    button_hover: Handle<AudioSource>,
+    button_press: Handle<AudioSource>,
+    menu_music: Handle<AudioSource>,
+    game_music: Handle<AudioSource>,
+    game_over_music: Handle<AudioSource>,
+    win_music: Handle<AudioSource>,
+}
+
+impl FromWorld for AudioAssets {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self {
+            boost_start: asset_server.load("audio/sfx/boost_start.wav"),
+            boost_stop: asset_server.load("audio/sfx/boost_stop.wav"),
+            fall: asset_server.load("audio/sfx/fall.wav"),
+            hit: asset_server.load("audio/sfx/hit.wav"),
+            coin: asset_server.load("audio/sfx/coin.wav"),
+            grow: asset_server.load("audio/sfx/grow.wav"),
+            shrink: asset_server.load("audio/sfx/shrink.wav"),
+            button_hover: asset_server.load("audio/sfx/button_hover.wav"),
+            button_press: asset_server.load("audio/sfx/button_press.wav"),
+            menu_music: asset_server.load("audio/music/menu.wav"),
+            game_music: asset_server.load("audio/music/game.wav"),
+            game_over_music: asset_server.load("audio/music/game_over.wav"),
+            win_music: asset_server.load("audio/music/win.wav"),
+        }
+    }
+}
+
+impl AudioAssets {
+    fn effect(&self, effect: SoundEffect) -> Handle<AudioSource> {
+        match effect {
+            SoundEffect::BoostStart => self.boost_start.clone(),
+            SoundEffect::BoostStop => self.boost_stop.clone(),
+            SoundEffect::Fall => self.fall.clone(),
+            SoundEffect::Hit => self.hit.clone(),
+            SoundEffect::CoinPickup => self.coin.clone(),
+            SoundEffect::Grow => self.grow.clone(),
+            SoundEffect::Shrink => self.shrink.clone(),
+            SoundEffect::ButtonHover => self.button_hover.clone(),
+            SoundEffect::ButtonPress => self.button_press.clone(),
+        }
+    }
+
+    fn music(&self, track: MusicTrack) -> Handle<AudioSource> {
+        match track {
+            MusicTrack::Menu => self.menu_music.clone(),
+            MusicTrack::Game => self.game_music.clone(),
+            MusicTrack::GameOver => self.game_over_music.clone(),
+            MusicTrack::Win => self.win_music.clone(),
+        }
+    }
+}
+
+/// The entity playing the current background loop.
+#[derive(Component)]
+pub struct MusicPlayer(pub MusicTrack);
+
+/// Volume of the effects bus: master times effects.
+pub fn sfx_volume(settings: &GameSettings) -> f32 {
+    settings.master_volume * settings.sfx_volume
+}
+
+/// Volume of the music bus: master times music.
+pub fn music_volume(settings: &GameSettings) -> f32 {
+    settings.master_volume * settings.music_volume
+}
+
+/// Plays sound effects and per-state music through the master, music and
+/// effects volume buses in `GameSettings`.
+pub struct GameAudioPlugin;
+
+impl Plugin for GameAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<AudioAssets>()
+            .add_systems(Update, (
+                play_button_sounds,
+                play_sound_effects,
+                switch_music.run_if(state_changed::<GameState>()),
+                apply_music_volume.run_if(resource_changed::<GameSettings>()),
+            ).chain());
+    }
+}
+
+/// Raises hover and press cues for every UI button, including focus moves
+/// made with the keyboard or a gamepad.
+fn play_button_sounds(
+    button_query: Query<&Interaction, (Changed<Interaction>, With<Button>)>,
+    focus: Res<MenuFocus>,
+    mut last_focused: Local<Option<Entity>>,
+    mut sounds: EventWriter<SoundEffect>,
+) {
+    for interaction in button_query.iter() {
+        match *interaction {
+            Interaction::Pressed => sounds.send(SoundEffect::ButtonPress),
+            Interaction::Hovered => sounds.send(SoundEffect::ButtonHover),
+            Interaction::None => {}
+        }
+    }
+
+    if focus.focused != *last_focused {
+        if focus.focused.is_some() {
+            sounds.send(SoundEffect::ButtonHover);
+        }
+        *last_focused = focus.focused;
+    }
+}
+
+fn play_sound_effects(
+    mut commands: Commands,
+    mut sounds: EventReader<SoundEffect>,
+    audio_assets: Res<AudioAssets>,
+    settings: Res<GameSettings>,
+) {
+    let volume = sfx_volume(&settings);
+    for effect in sounds.read() {
+        if volume <= 0.0 {
+            continue;
+        }
+        commands.spawn(AudioBundle {
+            source: audio_assets.effect(*effect),
+            settings: PlaybackSettings::DESPAWN.with_volume(Volume::new_relative(volume)),
+        });
+    }
+}
+
+/// Starts the loop for the new state, leaving it running when the track
+/// doesn't change (e.g. moving between menu screens).
+fn switch_music(
+    mut commands: Commands,
+    state: Res<State<GameState>>,
+    audio_assets: Res<AudioAssets>,
+    settings: Res<GameSettings>,
+    music_query: Query<(Entity, &MusicPlayer)>,
+) {
+    let track = MusicTrack::for_state(*state.get());
+    let mut playing = false;
+    for (entity, player) in music_query.iter() {
+        if Some(player.0) == track {
+            playing = true;
+        } else {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    if let Some(track) = track.filter(|_| !playing) {
+        commands.spawn((
+            AudioBundle {
+                source: audio_assets.music(track),
+                settings: PlaybackSettings {
+                    mode: PlaybackMode::Loop,
+                    ..default()
+                }
+                .with_volume(Volume::new_relative(music_volume(&settings))),
+            },
+            MusicPlayer(track),
+        ));
+    }
+}
+
+fn apply_music_volume(settings: Res<GameSettings>, music_query: Query<&AudioSink, With<MusicPlayer>>) {
+    for sink in music_query.iter() {
+        sink.set_volume(music_volume(&settings));
+    }
+}
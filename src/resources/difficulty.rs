@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+/// How hard the AI ducks play. Picked in the settings screen and saved with `GameSettings`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Brutal,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Brutal,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+            Difficulty::Brutal => "Brutal",
+        }
+    }
+
+    /// How this preset scales the AI tuning.
+    pub fn modifiers(self) -> DifficultyModifiers {
+        match self {
+            Difficulty::Easy => DifficultyModifiers {
+                reaction_time: 2.0,
+                aggression: 0.7,
+                boost_usage: 0.5,
+                max_speed: 0.8,
+                prediction: 0.0,
+            },
+            Difficulty::Normal => DifficultyModifiers::default(),
+            Difficulty::Hard => DifficultyModifiers {
+                reaction_time: 0.6,
+                aggression: 1.25,
+                boost_usage: 1.3,
+                max_speed: 1.1,
+                prediction: 1.5,
+            },
+            Difficulty::Brutal => DifficultyModifiers {
+                reaction_time: 0.35,
+                aggression: 1.5,
+                boost_usage: 1.7,
+                max_speed: 1.25,
+                prediction: 2.0,
+            },
+        }
+    }
+
+    /// The next preset, wrapping from the hardest back to the easiest.
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|difficulty| *difficulty == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// Multipliers a difficulty preset applies on top of the AI tuning. Normal
+/// plays the tuning file as written.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DifficultyModifiers {
+    /// Scales the AI's think and target intervals; lower reacts faster
+    pub reaction_time: f32,
+    /// Scales how much enemies want to chase and ram
+    pub aggression: f32,
+    /// Scales the range enemies start boost rams from
+    pub boost_usage: f32,
+    /// Scales enemy top speed
+    pub max_speed: f32,
+    /// Scales how far ahead enemies lead a moving target
+    pub prediction: f32,
+}
+
+impl Default for DifficultyModifiers {
+    fn default() -> Self {
+        Self {
+            reaction_time: 1.0,
+            aggression: 1.0,
+            boost_usage: 1.0,
+            max_speed: 1.0,
+            prediction: 1.0,
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+
+use bevy::{prelude::*, utils::HashSet};
+use serde::{Deserialize, Serialize};
+
+/// Logical actions gameplay and menus react to, independent of the physical keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum PlayerAction {
+    MoveForward,
+    MoveBack,
+    MoveLeft,
+    MoveRight,
+    Boost,
+    Pause,
+    Confirm,
+    Back,
+}
+
+impl PlayerAction {
+    pub const ALL: [PlayerAction; 8] = [
+        PlayerAction::MoveForward,
+        PlayerAction::MoveBack,
+        PlayerAction::MoveLeft,
+        PlayerAction::MoveRight,
+        PlayerAction::Boost,
+        PlayerAction::Pause,
+        PlayerAction::Confirm,
+        PlayerAction::Back,
+    ];
+}
+
+/// Where a player's actions come from.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputSource {
+    /// Every key set and every connected gamepad; menus and single-player matches use this
+    Any,
+    PrimaryKeys,
+    SecondaryKeys,
+    /// The nth connected gamepad, ordered by gamepad id
+    Gamepad(usize),
+}
+
+/// Stick deflection past which a stick also counts as pressing a move action,
+/// so menus can be navigated with the stick as well as the D-pad.
+pub const STICK_PRESS_THRESHOLD: f32 = 0.5;
+
+/// Which keys and gamepad buttons trigger which action. Saved as part of `GameSettings`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputBindings {
+    pub keys: BTreeMap<PlayerAction, Vec<KeyCode>>,
+    /// Keys for a second duck sharing the keyboard in local multiplayer
+    pub secondary_keys: BTreeMap<PlayerAction, Vec<KeyCode>>,
+    pub gamepad_buttons: BTreeMap<PlayerAction, Vec<GamepadButtonType>>,
+    /// Radial dead zone applied to the left stick, as a fraction of full deflection
+    pub stick_dead_zone: f32,
+    /// Device for each local player, by player index, when more than one duck is human
+    pub player_sources: Vec<InputSource>,
+}
+
+impl InputBindings {
+    pub fn keys_for(&self, action: PlayerAction) -> &[KeyCode] {
+        self.keys.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn secondary_keys_for(&self, action: PlayerAction) -> &[KeyCode] {
+        self.secondary_keys.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// A lone player listens to every device; in multiplayer each player gets their own.
+    pub fn source_for_player(&self, index: usize, player_count: usize) -> InputSource {
+        if player_count <= 1 {
+            return InputSource::Any;
+        }
+        self.player_sources
+            .get(index)
+            .copied()
+            .unwrap_or(InputSource::Gamepad(index))
+    }
+
+    pub fn gamepad_buttons_for(&self, action: PlayerAction) -> &[GamepadButtonType] {
+        self.gamepad_buttons.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Applies the radial dead zone and rescales the rest of the range back to `0..=1`.
+    pub fn apply_dead_zone(&self, stick: Vec2) -> Vec2 {
+        let length = stick.length();
+        if length <= self.stick_dead_zone {
+            return Vec2::ZERO;
+        }
+        let scaled = ((length - self.stick_dead_zone) / (1.0 - self.stick_dead_zone)).min(1.0);
+        stick / length * scaled
+    }
+
+    /// Replaces every key bound to `action` with `key`.
+    pub fn rebind(&mut self, action: PlayerAction, key: KeyCode) {
+        self.keys.insert(action, vec![key]);
+    }
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        let keys = BTreeMap::from([
+            (PlayerAction::MoveForward, vec![KeyCode::W]),
+            (PlayerAction::MoveBack, vec![KeyCode::S]),
+            (PlayerAction::MoveLeft, vec![KeyCode::A]),
+            (PlayerAction::MoveRight, vec![KeyCode::D]),
+            (PlayerAction::Boost, vec![KeyCode::Space]),
+            (PlayerAction::Pause, vec![KeyCode::Escape]),
+            (PlayerAction::Confirm, vec![KeyCode::Return]),
+            (PlayerAction::Back, vec![KeyCode::Escape, KeyCode::Back]),
+        ]);
+        let secondary_keys = BTreeMap::from([
+            (PlayerAction::MoveForward, vec![KeyCode::Up]),
+            (PlayerAction::MoveBack, vec![KeyCode::Down]),
+            (PlayerAction::MoveLeft, vec![KeyCode::Left]),
+            (PlayerAction::MoveRight, vec![KeyCode::Right]),
+            (PlayerAction::Boost, vec![KeyCode::ShiftRight, KeyCode::ControlRight]),
+        ]);
+        let gamepad_buttons = BTreeMap::from([
+            (PlayerAction::MoveForward, vec![GamepadButtonType::DPadUp]),
+            (PlayerAction::MoveBack, vec![GamepadButtonType::DPadDown]),
+            (PlayerAction::MoveLeft, vec![GamepadButtonType::DPadLeft]),
+            (PlayerAction::MoveRight, vec![GamepadButtonType::DPadRight]),
+            (PlayerAction::Boost, vec![GamepadButtonType::RightTrigger2, GamepadButtonType::RightTrigger]),
+            (PlayerAction::Pause, vec![GamepadButtonType::Start]),
+            (PlayerAction::Confirm, vec![GamepadButtonType::South]),
+            (PlayerAction::Back, vec![GamepadButtonType::East]),
+        ]);
+        Self {
+            keys,
+            secondary_keys,
+            gamepad_buttons,
+            stick_dead_zone: 0.2,
+            player_sources: vec![
+                InputSource::PrimaryKeys,
+                InputSource::SecondaryKeys,
+                InputSource::Gamepad(0),
+                InputSource::Gamepad(1),
+            ],
+        }
+    }
+}
+
+/// Current state of every action, rebuilt once per frame from the bindings.
+/// The resource merges every device for menus; each player also carries one
+/// for their own `InputSource`.
+#[derive(Resource, Component, Debug, Default)]
+pub struct ActionState {
+    pressed: HashSet<PlayerAction>,
+    just_pressed: HashSet<PlayerAction>,
+    just_released: HashSet<PlayerAction>,
+    /// Left stick after the dead zone, `y` pointing forward
+    stick: Vec2,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: PlayerAction) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: PlayerAction) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    pub fn just_released(&self, action: PlayerAction) -> bool {
+        self.just_released.contains(&action)
+    }
+
+    /// Movement on the XZ plane: `x` is right, `y` is forward (-Z).
+    /// The analog stick wins over digital input when it is deflected.
+    pub fn move_axis(&self) -> Vec2 {
+        if self.stick != Vec2::ZERO {
+            return self.stick;
+        }
+
+        let mut axis = Vec2::ZERO;
+        if self.pressed(PlayerAction::MoveForward) {
+            axis.y += 1.0;
+        }
+        if self.pressed(PlayerAction::MoveBack) {
+            axis.y -= 1.0;
+        }
+        if self.pressed(PlayerAction::MoveLeft) {
+            axis.x -= 1.0;
+        }
+        if self.pressed(PlayerAction::MoveRight) {
+            axis.x += 1.0;
+        }
+        axis
+    }
+
+    /// Replaces the held set and stick, and derives this frame's press and release edges.
+    pub fn update(&mut self, mut pressed: HashSet<PlayerAction>, stick: Vec2) {
+        if stick.y >= STICK_PRESS_THRESHOLD {
+            pressed.insert(PlayerAction::MoveForward);
+        }
+        if stick.y <= -STICK_PRESS_THRESHOLD {
+            pressed.insert(PlayerAction::MoveBack);
+        }
+        if stick.x <= -STICK_PRESS_THRESHOLD {
+            pressed.insert(PlayerAction::MoveLeft);
+        }
+        if stick.x >= STICK_PRESS_THRESHOLD {
+            pressed.insert(PlayerAction::MoveRight);
+        }
+
+        self.stick = stick;
+        self.just_pressed = pressed.difference(&self.pressed).copied().collect();
+        self.just_released = self.pressed.difference(&pressed).copied().collect();
+        self.pressed = pressed;
+    }
+}
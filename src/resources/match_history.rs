@@ -0,0 +1,288 @@
+use std::{fmt, path::PathBuf, time::Duration};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::components::{Contestant, GameSettings, Player, MAX_LOCAL_PLAYERS};
+use crate::resources::{Difficulty, GameRng};
+use crate::states::GameState;
+
+/// Oldest matches are dropped once the history grows past this.
+pub const MAX_HISTORY_RECORDS: usize = 200;
+
+#[cfg(not(target_arch = "wasm32"))]
+const HISTORY_FILE_NAME: &str = "history.ron";
+
+/// One duck's line in a finished match's final standings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StandingEntry {
+    pub name: String,
+    pub human: bool,
+    pub points: i32,
+    pub lives: u32,
+    pub falls: u32,
+    #[serde(default)]
+    pub knockouts: u32,
+    pub eliminations: u32,
+    #[serde(default)]
+    pub best_streak: u32,
+}
+
+impl StandingEntry {
+    pub fn new(contestant: &Contestant, human: bool) -> Self {
+        Self {
+            name: contestant.name.clone(),
+            human,
+            points: contestant.points,
+            lives: contestant.lives,
+            falls: contestant.falls,
+            knockouts: contestant.knockouts,
+            eliminations: contestant.eliminations,
+            best_streak: contestant.best_streak,
+        }
+    }
+}
+
+/// The line logged for this duck when a match ends.
+impl fmt::Display for StandingEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} pts, {} lives, {} falls, {} knockouts, {} eliminations, best streak {}",
+            self.name, self.points, self.lives, self.falls, self.knockouts, self.eliminations, self.best_streak
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MatchOutcome {
+    Won,
+    Lost,
+}
+
+/// A finished match as kept in the local history file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchRecord {
+    /// Seconds since the Unix epoch, UTC
+    pub timestamp: u64,
+    pub player_count: usize,
+    pub difficulty: Difficulty,
+    pub seed: u64,
+    pub outcome: MatchOutcome,
+    /// Best finishing place of a human duck, starting at 1
+    pub placement: usize,
+    pub duration_secs: f32,
+    /// Every duck, best first
+    pub standings: Vec<StandingEntry>,
+}
+
+impl MatchRecord {
+    /// Highest score any human duck finished the match with.
+    pub fn best_score(&self) -> Option<i32> {
+        self.standings
+            .iter()
+            .filter(|entry| entry.human)
+            .map(|entry| entry.points)
+            .max()
+    }
+
+    pub fn mode_label(&self) -> String {
+        let players = if self.player_count == 1 {
+            "Solo".to_string()
+        } else {
+            format!("{} Players", self.player_count)
+        };
+        format!("{}, {}", players, self.difficulty.label())
+    }
+
+    /// `YYYY-MM-DD HH:MM` in UTC.
+    pub fn date_label(&self) -> String {
+        let days = (self.timestamp / 86_400) as i64;
+        let seconds_of_day = self.timestamp % 86_400;
+        let (year, month, day) = civil_from_days(days);
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}",
+            year,
+            month,
+            day,
+            seconds_of_day / 3600,
+            seconds_of_day % 3600 / 60
+        )
+    }
+
+    pub fn duration_label(&self) -> String {
+        let seconds = self.duration_secs.round() as u64;
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+/// Converts days since 1970-01-01 to a (year, month, day) Gregorian date.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now_timestamp() -> u64 {
+    0
+}
+
+/// Every finished match, oldest first, and where to save them.
+#[derive(Resource, Default)]
+pub struct MatchHistory {
+    pub records: Vec<MatchRecord>,
+    /// Matches are kept for the session but not saved when `None`
+    pub path: Option<PathBuf>,
+}
+
+impl MatchHistory {
+    /// Location of the history file in the user's data directory.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join(super::CONFIG_DIR_NAME).join(HISTORY_FILE_NAME))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn default_path() -> Option<PathBuf> {
+        None
+    }
+
+    /// Reads the history at `path`, starting empty when there is none yet.
+    /// Runs before logging is set up, so problems go to stderr.
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let records = path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok().map(|contents| (path, contents)))
+            .map(|(path, contents)| {
+                ron::from_str(&contents).unwrap_or_else(|err| {
+                    eprintln!("Ignoring invalid match history {}: {}", path.display(), err);
+                    Vec::new()
+                })
+            })
+            .unwrap_or_default();
+        Self { records, path }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let contents = ron::ser::to_string_pretty(&self.records, ron::ser::PrettyConfig::default())?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    pub fn best_score(&self) -> Option<i32> {
+        self.records.iter().filter_map(MatchRecord::best_score).max()
+    }
+}
+
+/// Game time spent in the current match, excluding pauses.
+#[derive(Resource, Default)]
+pub struct MatchClock(pub Duration);
+
+/// The match that just ended, for the game-over and win screens.
+#[derive(Resource, Default)]
+pub struct MatchResult {
+    pub record: Option<MatchRecord>,
+    /// The best human score beat every earlier match in the history
+    pub new_record: bool,
+}
+
+/// Starts a new match with the clock at zero and no result yet.
+pub fn reset_match_clock(mut clock: ResMut<MatchClock>, mut result: ResMut<MatchResult>) {
+    clock.0 = Duration::ZERO;
+    *result = MatchResult::default();
+}
+
+pub fn tick_match_clock(time: Res<Time>, mut clock: ResMut<MatchClock>) {
+    clock.0 += time.delta();
+}
+
+/// Snapshots the final standings into `MatchResult` and the history as the
+/// match ends, before the match entities are cleaned up.
+pub fn record_match_result(
+    state: Res<State<GameState>>,
+    contestant_query: Query<(&Contestant, Option<&Player>)>,
+    settings: Res<GameSettings>,
+    game_rng: Res<GameRng>,
+    clock: Res<MatchClock>,
+    mut history: ResMut<MatchHistory>,
+    mut result: ResMut<MatchResult>,
+) {
+    let mut standings = contestant_query
+        .iter()
+        .map(|(contestant, player)| StandingEntry::new(contestant, player.is_some()))
+        .collect::<Vec<_>>();
+    standings.sort_by(|a, b| {
+        (a.lives == 0)
+            .cmp(&(b.lives == 0))
+            .then(b.points.cmp(&a.points))
+    });
+
+    let placement = standings
+        .iter()
+        .position(|entry| entry.human)
+        .map_or(standings.len(), |index| index + 1);
+
+    let record = MatchRecord {
+        timestamp: now_timestamp(),
+        player_count: settings.player_count.clamp(1, MAX_LOCAL_PLAYERS),
+        difficulty: settings.difficulty,
+        seed: game_rng.seed(),
+        outcome: if *state.get() == GameState::WinScreen {
+            MatchOutcome::Won
+        } else {
+            MatchOutcome::Lost
+        },
+        placement,
+        duration_secs: clock.0.as_secs_f32(),
+        standings,
+    };
+
+    result.new_record = match (record.best_score(), history.best_score()) {
+        (Some(score), Some(best)) => score > best,
+        (Some(_), None) => true,
+        (None, _) => false,
+    };
+
+    for entry in &record.standings {
+        info!("{}", entry);
+    }
+
+    history.records.push(record.clone());
+    if history.records.len() > MAX_HISTORY_RECORDS {
+        let excess = history.records.len() - MAX_HISTORY_RECORDS;
+        history.records.drain(..excess);
+    }
+    if let Err(err) = history.save() {
+        warn!("Failed to save match history: {}", err);
+    }
+
+    result.record = Some(record);
+}
+
+/// Run condition for `OnExit(GameState::InGame)`: the match was decided
+/// rather than paused or abandoned.
+pub fn match_decided(state: Res<State<GameState>>) -> bool {
+    matches!(state.get(), GameState::GameOver | GameState::WinScreen)
+}
@@ -1,3 +1,21 @@
 pub mod winter_bg;
+pub mod rng;
+pub mod tuning;
+pub mod input;
+pub mod settings_file;
+pub mod replay;
+pub mod difficulty;
+pub mod match_history;
+pub mod audio;
+pub mod arena;
 
 pub use winter_bg::*;
+pub use rng::*;
+pub use tuning::*;
+pub use input::*;
+pub use settings_file::*;
+pub use replay::*;
+pub use difficulty::*;
+pub use match_history::*;
+pub use audio::*;
+pub use arena::*;
@@ -0,0 +1,416 @@
+use std::path::{Path, PathBuf};
+
+use bevy::{app::AppExit, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::components::{GameSettings, Player, PlayerInput, MAX_LOCAL_PLAYERS};
+use crate::plugins::run_if_resuming_from_pause;
+use crate::resources::{arenas_ready, seed_match_rng, tuning_ready, ArenaChoice, Difficulty, GameRng};
+use crate::states::GameState;
+use crate::systems::GameMechanicsSet;
+
+/// Bumped whenever the file layout, the inputs it records or the random
+/// stream salts change meaning.
+pub const REPLAY_FORMAT_VERSION: u32 = 2;
+
+#[cfg(not(target_arch = "wasm32"))]
+const REPLAY_DIR_NAME: &str = "replays";
+#[cfg(not(target_arch = "wasm32"))]
+const LAST_REPLAY_FILE_NAME: &str = "last.replay.ron";
+
+/// A run of consecutive fixed ticks with identical input for every player.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplaySpan {
+    pub ticks: u32,
+    /// One entry per local player, by player index
+    pub inputs: Vec<PlayerInput>,
+}
+
+/// A recorded match: the match seed plus every player's input on every fixed
+/// tick. Playing the inputs back against the same seed, tuning and build
+/// reproduces the match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub player_count: usize,
+    /// Replays recorded before difficulty presets existed played on Normal
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    /// Replays recorded before arenas could be picked played on the circle
+    #[serde(default)]
+    pub arena: ArenaChoice,
+    #[serde(default)]
+    pub sudden_death: bool,
+    #[serde(default)]
+    pub tilting: bool,
+    /// Run-length encoded per-tick input, in tick order
+    pub spans: Vec<ReplaySpan>,
+}
+
+impl Replay {
+    pub fn new(settings: &GameSettings, seed: u64, player_count: usize) -> Self {
+        Self {
+            version: REPLAY_FORMAT_VERSION,
+            seed,
+            player_count,
+            difficulty: settings.difficulty,
+            arena: settings.arena,
+            sudden_death: settings.sudden_death,
+            tilting: settings.tilting,
+            spans: Vec::new(),
+        }
+    }
+
+    pub fn push_tick(&mut self, inputs: Vec<PlayerInput>) {
+        match self.spans.last_mut() {
+            Some(span) if span.inputs == inputs => span.ticks += 1,
+            _ => self.spans.push(ReplaySpan { ticks: 1, inputs }),
+        }
+    }
+
+    pub fn tick_count(&self) -> u64 {
+        self.spans.iter().map(|span| u64::from(span.ticks)).sum()
+    }
+
+    /// Makes the next match start from the recorded seed, player count,
+    /// difficulty, arena and mode.
+    pub fn apply_to(&self, settings: &mut GameSettings) {
+        settings.seed = Some(self.seed);
+        settings.player_count = self.player_count;
+        settings.difficulty = self.difficulty;
+        settings.arena = self.arena;
+        settings.sudden_death = self.sudden_death;
+        settings.tilting = self.tilting;
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let replay: Replay = ron::from_str(&std::fs::read_to_string(path)?)?;
+        if replay.version != REPLAY_FORMAT_VERSION {
+            return Err(format!(
+                "replay format version {} is not supported (expected {})",
+                replay.version, REPLAY_FORMAT_VERSION
+            )
+            .into());
+        }
+        Ok(replay)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+/// Where the windowed game saves the most recent match when no `--record` path is given.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn last_replay_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| {
+        dir.join(super::CONFIG_DIR_NAME)
+            .join(REPLAY_DIR_NAME)
+            .join(LAST_REPLAY_FILE_NAME)
+    })
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn last_replay_path() -> Option<PathBuf> {
+    None
+}
+
+/// Records the current match and saves it when the match ends.
+#[derive(Resource, Default)]
+pub struct ReplayRecorder {
+    /// Save location; matches are recorded but not saved when `None`
+    pub path: Option<PathBuf>,
+    replay: Option<Replay>,
+}
+
+impl ReplayRecorder {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self { path, replay: None }
+    }
+}
+
+/// Feeds a recorded match into `PlayerInput` instead of the live devices.
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    replay: Replay,
+    span: usize,
+    ticks_into_span: u32,
+    started: bool,
+    finished: bool,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            span: 0,
+            ticks_into_span: 0,
+            started: false,
+            finished: false,
+        }
+    }
+
+    /// Inputs for the next fixed tick, or `None` once the recording has run out.
+    fn next_tick(&mut self) -> Option<&[PlayerInput]> {
+        let span = self.replay.spans.get(self.span)?;
+        self.ticks_into_span += 1;
+        if self.ticks_into_span >= span.ticks {
+            self.span += 1;
+            self.ticks_into_span = 0;
+        }
+        Some(&span.inputs)
+    }
+}
+
+/// Records every match tick by tick, and plays a replay back instead when a
+/// `ReplayPlayback` resource is present.
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ReplayRecorder>()
+            .add_systems(
+                OnEnter(GameState::InGame),
+                start_replay_recording
+                    .after(seed_match_rng)
+                    .run_if(not(run_if_resuming_from_pause))
+                    .run_if(not(resource_exists::<ReplayPlayback>())),
+            )
+            .add_systems(
+                Update,
+                start_replay_match
+                    .run_if(in_state(GameState::MainMenu))
+                    .run_if(resource_exists::<ReplayPlayback>())
+                    .run_if(tuning_ready)
+                    .run_if(arenas_ready),
+            )
+            .add_systems(FixedUpdate, (
+                play_replay_tick.run_if(resource_exists::<ReplayPlayback>()),
+                record_replay_tick,
+            )
+                .chain()
+                .before(GameMechanicsSet::Movement)
+                .run_if(in_state(GameState::InGame)))
+            .add_systems(OnEnter(GameState::GameOver), (save_replay, end_replay_playback))
+            .add_systems(OnEnter(GameState::WinScreen), (save_replay, end_replay_playback))
+            .add_systems(OnEnter(GameState::MainMenu), (save_replay, end_replay_playback))
+            .add_systems(Last, save_replay.run_if(on_event::<AppExit>()));
+    }
+}
+
+fn start_replay_recording(
+    settings: Res<GameSettings>,
+    game_rng: Res<GameRng>,
+    mut recorder: ResMut<ReplayRecorder>,
+) {
+    let player_count = settings.player_count.clamp(1, MAX_LOCAL_PLAYERS);
+    recorder.replay = Some(Replay::new(&settings, game_rng.seed(), player_count));
+}
+
+/// Starts the recorded match straight away instead of waiting on the menu.
+fn start_replay_match(
+    mut playback: ResMut<ReplayPlayback>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !playback.started {
+        playback.started = true;
+        info!(
+            "Playing back replay: seed {}, {} ticks",
+            playback.replay.seed,
+            playback.replay.tick_count()
+        );
+        next_state.set(GameState::InGame);
+    }
+}
+
+/// Hands the players back to live input once the replayed match is over.
+fn end_replay_playback(mut commands: Commands, playback: Option<Res<ReplayPlayback>>) {
+    if playback.is_some_and(|playback| playback.started) {
+        commands.remove_resource::<ReplayPlayback>();
+    }
+}
+
+fn play_replay_tick(
+    mut playback: ResMut<ReplayPlayback>,
+    mut player_query: Query<(&Player, &mut PlayerInput)>,
+) {
+    let inputs = playback.next_tick().map(<[PlayerInput]>::to_vec);
+    if inputs.is_none() && !playback.finished {
+        playback.finished = true;
+        info!("Replay finished");
+    }
+
+    for (player, mut input) in player_query.iter_mut() {
+        *input = inputs
+            .as_ref()
+            .and_then(|inputs| inputs.get(player.index).cloned())
+            .unwrap_or_default();
+    }
+}
+
+fn record_replay_tick(
+    mut recorder: ResMut<ReplayRecorder>,
+    player_query: Query<(&Player, &PlayerInput)>,
+) {
+    let Some(replay) = recorder.replay.as_mut() else {
+        return;
+    };
+
+    let mut players = player_query.iter().collect::<Vec<_>>();
+    players.sort_by_key(|(player, _)| player.index);
+    replay.push_tick(players.into_iter().map(|(_, input)| input.clone()).collect());
+}
+
+/// Writes the recorded match out once it is over or abandoned.
+fn save_replay(mut recorder: ResMut<ReplayRecorder>) {
+    let Some(replay) = recorder.replay.take() else {
+        return;
+    };
+    let Some(path) = recorder.path.as_ref() else {
+        return;
+    };
+
+    match replay.save(path) {
+        Ok(()) => info!("Saved replay of {} ticks to {}", replay.tick_count(), path.display()),
+        Err(err) => warn!("Failed to save replay to {}: {}", path.display(), err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+    use bevy_rapier3d::prelude::Velocity;
+
+    use super::*;
+    use crate::components::{Contestant, Enemy, PauseState};
+    use crate::plugins::{headless_app, start_headless_match};
+
+    /// Ticks recorded before the pause, and in the whole match.
+    const TICKS_BEFORE_PAUSE: u64 = 200;
+    const MATCH_TICKS: u64 = 420;
+    /// Updates spent paused; the ducks would move a second's worth if physics kept going.
+    const PAUSED_UPDATES: usize = 120;
+
+    /// Name, position, velocity, points and lives of every duck, by name.
+    type Snapshot = Vec<(String, Vec3, Vec3, i32, u32)>;
+
+    /// Steps time half a fixed tick per update, so no update runs two ticks
+    /// and the recording and the playback stop on the same one.
+    fn half_tick_frames(app: &mut App) {
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(1.0 / 120.0)));
+    }
+
+    fn recorded_ticks(app: &App) -> u64 {
+        app.world
+            .resource::<ReplayRecorder>()
+            .replay
+            .as_ref()
+            .map_or(0, Replay::tick_count)
+    }
+
+    fn played_ticks(app: &App) -> u64 {
+        let playback = app.world.resource::<ReplayPlayback>();
+        let spans = &playback.replay.spans[..playback.span.min(playback.replay.spans.len())];
+        spans.iter().map(|span| u64::from(span.ticks)).sum::<u64>() + u64::from(playback.ticks_into_span)
+    }
+
+    /// Pauses or resumes the way the pause key does.
+    fn set_paused(app: &mut App, paused: bool) {
+        let mut pause_state = app.world.resource_mut::<PauseState>();
+        pause_state.transitioning_to_pause = paused;
+        pause_state.was_paused = true;
+        let state = if paused { GameState::Paused } else { GameState::InGame };
+        app.world.resource_mut::<NextState<GameState>>().set(state);
+    }
+
+    fn snapshot(app: &mut App) -> Snapshot {
+        let mut ducks = app
+            .world
+            .query_filtered::<(&Contestant, &Transform, &Velocity), Or<(With<Player>, With<Enemy>)>>()
+            .iter(&app.world)
+            .map(|(contestant, transform, velocity)| {
+                (
+                    contestant.name.clone(),
+                    transform.translation,
+                    velocity.linvel,
+                    contestant.points,
+                    contestant.lives,
+                )
+            })
+            .collect::<Vec<_>>();
+        ducks.sort_by(|a, b| a.0.cmp(&b.0));
+        ducks
+    }
+
+    #[test]
+    fn paused_match_replays_to_the_same_final_state() {
+        let settings = GameSettings {
+            seed: Some(42),
+            ..default()
+        };
+        let mut recording = headless_app(settings, ReplayRecorder::new(None), None);
+        half_tick_frames(&mut recording);
+        start_headless_match(&mut recording);
+
+        recording.world.resource_mut::<Input<KeyCode>>().press(KeyCode::D);
+        while recorded_ticks(&recording) < TICKS_BEFORE_PAUSE {
+            recording.update();
+        }
+        set_paused(&mut recording, true);
+        for _ in 0..PAUSED_UPDATES {
+            recording.update();
+        }
+        assert_eq!(recorded_ticks(&recording), TICKS_BEFORE_PAUSE);
+        set_paused(&mut recording, false);
+        {
+            let mut keyboard = recording.world.resource_mut::<Input<KeyCode>>();
+            keyboard.release(KeyCode::D);
+            keyboard.press(KeyCode::W);
+        }
+        while recorded_ticks(&recording) < MATCH_TICKS {
+            recording.update();
+        }
+        let recorded = snapshot(&mut recording);
+        assert!(recorded.len() > 1, "no enemies joined the match");
+        let replay = recording.world.resource::<ReplayRecorder>().replay.clone().unwrap();
+
+        let mut settings = GameSettings::default();
+        replay.apply_to(&mut settings);
+        let mut playback = headless_app(settings, ReplayRecorder::new(None), Some(replay));
+        half_tick_frames(&mut playback);
+        start_headless_match(&mut playback);
+        while played_ticks(&playback) < MATCH_TICKS {
+            playback.update();
+        }
+        let replayed = snapshot(&mut playback);
+
+        assert_eq!(recorded.len(), replayed.len());
+        for (recorded, replayed) in recorded.iter().zip(&replayed) {
+            assert_eq!(recorded.0, replayed.0);
+            assert!(
+                recorded.1.abs_diff_eq(replayed.1, 1e-4),
+                "{} ended at {} but replayed to {}",
+                recorded.0,
+                recorded.1,
+                replayed.1
+            );
+            assert!(
+                recorded.2.abs_diff_eq(replayed.2, 1e-4),
+                "{} ended moving {} but replayed moving {}",
+                recorded.0,
+                recorded.2,
+                replayed.2
+            );
+            assert_eq!((recorded.3, recorded.4), (replayed.3, replayed.4), "{} scored differently", recorded.0);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use bevy::{prelude::*, utils::HashMap};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::components::GameSettings;
+
+/// Independent random streams, one per gameplay concern, so that adding a
+/// roll in one system does not shift the numbers every other system sees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RngStream {
+    EnemyAi,
+    Spawning,
+    PowerUps,
+}
+
+impl RngStream {
+    /// Mixed into the match seed to derive this stream. Fixed per stream so
+    /// adding or removing a variant leaves the other streams' numbers alone;
+    /// never reuse a retired salt (2 belonged to the old boost stream), and
+    /// bump `REPLAY_FORMAT_VERSION` when a salt changes.
+    fn salt(self) -> u64 {
+        let index: u64 = match self {
+            RngStream::EnemyAi => 1,
+            RngStream::Spawning => 3,
+            RngStream::PowerUps => 4,
+        };
+        index.wrapping_mul(0x9E37_79B9_7F4A_7C15)
+    }
+}
+
+/// The single source of gameplay randomness. Reseeded at the start of every
+/// match; the same seed and inputs replay the same match.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    streams: HashMap<RngStream, StdRng>,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            streams: HashMap::default(),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.streams.clear();
+    }
+
+    /// Returns the generator for `stream`, deriving it from the match seed on first use.
+    pub fn stream(&mut self, stream: RngStream) -> &mut StdRng {
+        let seed = self.seed;
+        self.streams
+            .entry(stream)
+            .or_insert_with(|| StdRng::seed_from_u64(seed ^ stream.salt()))
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+/// Seeds the match from `GameSettings::seed`, or a fresh random seed when none is set.
+pub fn seed_match_rng(settings: Res<GameSettings>, mut rng: ResMut<GameRng>) {
+    let seed = settings.seed.unwrap_or_else(|| rand::thread_rng().gen());
+    rng.reseed(seed);
+    info!("Match seed: {}", seed);
+}
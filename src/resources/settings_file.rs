@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+
+use crate::components::GameSettings;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) const CONFIG_DIR_NAME: &str = "bevy-duck-balance";
+#[cfg(not(target_arch = "wasm32"))]
+const SETTINGS_FILE_NAME: &str = "settings.ron";
+
+/// Location of the settings file in the user's config directory.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn settings_path() -> Option<std::path::PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(CONFIG_DIR_NAME).join(SETTINGS_FILE_NAME))
+}
+
+/// Reads the saved settings, falling back to defaults when there are none yet.
+/// Runs before logging is set up, so problems go to stderr.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_settings() -> GameSettings {
+    let Some(path) = settings_path() else {
+        return GameSettings::default();
+    };
+
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => ron::from_str(&contents).unwrap_or_else(|err| {
+            eprintln!("Ignoring invalid settings file {}: {}", path.display(), err);
+            GameSettings::default()
+        }),
+        Err(_) => GameSettings::default(),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn load_settings() -> GameSettings {
+    GameSettings::default()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_settings(settings: &GameSettings) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(path) = settings_path() else {
+        return Ok(());
+    };
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let contents = ron::ser::to_string_pretty(settings, ron::ser::PrettyConfig::default())?;
+    std::fs::write(path, contents)?;
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save_settings(_settings: &GameSettings) -> Result<(), Box<dyn std::error::Error>> {
+    Ok(())
+}
+
+/// Writes `GameSettings` back to disk whenever it changes.
+pub fn persist_settings(settings: Res<GameSettings>) {
+    if let Err(err) = save_settings(&settings) {
+        warn!("Failed to save settings: {}", err);
+    }
+}
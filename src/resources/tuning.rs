@@ -0,0 +1,540 @@
+use std::time::Duration;
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext, LoadState},
+    prelude::*,
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+
+use crate::components::{Enemy, EnergyBoost, GameSettings, Health};
+use crate::resources::Difficulty;
+
+pub const TUNING_PATH: &str = "tuning/game.tuning.ron";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlayerTuning {
+    pub movement_force: f32,
+    /// Multiplier on `movement_force` while walking
+    pub movement_acceleration: f32,
+    /// Multiplier on `movement_force` while boosting
+    pub boost_acceleration: f32,
+    pub max_speed: f32,
+    pub boost_max_speed: f32,
+    /// Exponential decay rate (1/s) applied when there is no input
+    pub idle_deceleration_rate: f32,
+    /// Additional downward acceleration when falling
+    pub fall_acceleration: f32,
+    /// Fastest a duck turns toward where it is going, in degrees per second
+    pub turn_rate: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnemyTuning {
+    pub movement_force: f32,
+    pub max_speed: f32,
+    /// Multiplier on `movement_force` while boosting
+    pub boost_acceleration: f32,
+    pub boost_max_speed: f32,
+    /// Exponential decay rate (1/s) applied every tick
+    pub friction_rate: f32,
+    pub fall_acceleration: f32,
+    /// Fastest an enemy turns toward where it is going, in degrees per second
+    pub turn_rate: f32,
+    /// Seconds between utility AI decisions. Enemies only re-score their
+    /// options this often and keep steering at their last choice in between,
+    /// so lower values react faster but dither more. Scaled by the
+    /// difficulty's reaction time
+    pub think_interval: f32,
+    /// Seconds an enemy sticks with its target before picking again, unless
+    /// the target falls first. Scaled by the difficulty's reaction time
+    pub target_interval: f32,
+    pub respawn_delay: f32,
+    /// Score lead another option needs before an enemy drops its current one
+    pub switch_margin: f32,
+    /// Fraction of the way from the safest spot to the edge where the edge starts to feel dangerous
+    pub edge_caution_start: f32,
+    /// Power-up coins further away than this are ignored
+    pub powerup_range: f32,
+    /// Ducks further away than this are not worth a boost ram
+    pub ram_range: f32,
+    /// Largest angle, in degrees, between an enemy's heading and its target for a ram to start
+    pub ram_aim_tolerance: f32,
+    /// How far past its target a ram is expected to carry an enemy
+    pub ram_overrun: f32,
+    /// How sharply enemies correct towards their desired velocity (1/s)
+    pub steering_response: f32,
+    /// Enemies start slowing down this far from a spot they are heading to
+    pub arrival_radius: f32,
+    /// Enemies steer apart when closer than this to another duck
+    pub separation_radius: f32,
+    pub separation_weight: f32,
+    /// Enemies steer to keep this far inside the platform edge
+    pub edge_margin: f32,
+    /// Seconds ahead an enemy checks its own course against the edge
+    pub edge_lookahead: f32,
+    pub edge_weight: f32,
+    /// Longest look-ahead, in seconds, when leading a moving target
+    pub max_prediction: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BoostTuning {
+    /// Energy consumed per second while boosting
+    pub energy_consumption_rate: f32,
+    /// Energy regained per second once the recharge delay has passed
+    pub recharge_rate: f32,
+    pub min_energy_to_start: f32,
+    pub cooldown: f32,
+    pub recharge_delay: f32,
+    /// Seconds a duck telegraphs a boost, braking, before it pushes off
+    pub windup: f32,
+    /// How quickly a winding-up duck sheds speed
+    pub windup_brake: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HealthTuning {
+    /// Health of player ducks; enemies roll their own between `enemy_min_health` and `enemy_max_health`
+    pub player_health: f32,
+    pub enemy_min_health: f32,
+    pub enemy_max_health: f32,
+    /// Damage every hit deals
+    pub hit_damage: f32,
+    /// Extra damage per unit of closing speed
+    pub damage_per_speed: f32,
+    /// Damage multiplier for the duck that was rammed by a boosting duck
+    pub ram_damage_multiplier: f32,
+    /// Health regained per second once recovery starts
+    pub recovery_rate: f32,
+    /// Seconds after a hit before health starts to recover
+    pub recovery_delay: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CombatTuning {
+    /// Contacts closing slower than this are bumps, not hits
+    pub min_hit_speed: f32,
+    /// Velocity change every hit knocks each duck away with
+    pub push_force: f32,
+    /// Extra knockback per unit of closing speed
+    pub push_per_speed: f32,
+    /// Knockback multiplier for the duck that was rammed by a boosting duck
+    pub ram_push_multiplier: f32,
+    /// Extra knockback multiplier for a duck with no health left
+    pub weak_push_bonus: f32,
+    /// Seconds a knocked-back duck loses control for
+    pub hit_stun: f32,
+    /// Seconds a rammed duck loses control for
+    pub ram_hit_stun: f32,
+    /// Seconds after a shove during which a fall still counts as a knockout
+    pub attribution_window: f32,
+}
+
+/// One wave of enemies.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WaveDefinition {
+    pub enemies: usize,
+    /// Lives every enemy in the wave gets
+    pub lives: u32,
+    /// Multiplier on the rolled enemy health
+    pub health_multiplier: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WaveTuning {
+    /// Seconds before the first wave is announced
+    pub first_delay: f32,
+    /// Seconds of calm between clearing a wave and announcing the next
+    pub breather: f32,
+    /// Seconds spawn points are marked before a wave's enemies appear
+    pub telegraph: f32,
+    /// Played in order; the match is won once the last one is cleared.
+    /// Must not be empty, or there would be no wave to clear
+    pub waves: Vec<WaveDefinition>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SuddenDeathTuning {
+    /// Seconds into the match before the platform first shrinks
+    pub start_after: f32,
+    /// Shrinking starts early once only this many ducks are left in the match
+    pub stalemate_ducks: usize,
+    /// Seconds between one shrink and the next stage's warning
+    pub stage_interval: f32,
+    /// Seconds the new edge is marked before the platform shrinks to it
+    pub telegraph: f32,
+    /// Seconds the platform takes to shrink to the new edge
+    pub shrink_time: f32,
+    /// Fraction of the full size lost per stage
+    pub shrink_per_stage: f32,
+    /// Fraction of the full size the platform never shrinks below
+    pub min_scale: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TiltTuning {
+    /// Tilt acceleration (rad/s²) per unit of duck weight per metre from the
+    /// pivot. A duck weighs its size cubed, so grown ducks tip it much further
+    pub weight_torque: f32,
+    /// Tilt acceleration (rad/s²) per radian pulling the platform back level
+    pub stiffness: f32,
+    /// Fraction of the tilting speed lost per second
+    pub damping: f32,
+    /// Degrees the platform can tilt at most
+    pub max_angle: f32,
+    /// Downhill push on ducks standing on the platform, per unit of slope
+    pub slide_acceleration: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RulesTuning {
+    /// Lives every player starts a match with; enemies get theirs from their wave
+    pub starting_lives: u32,
+    /// Points for knocking a duck off the platform
+    pub knockout_points: i32,
+    /// Extra points per earlier knockout in a streak
+    pub streak_bonus: i32,
+}
+
+/// Gameplay constants, loaded from `assets/tuning/game.tuning.ron` and
+/// hot-reloaded while the game runs. The defaults mirror the shipped file
+/// and are used until it has loaded, or when no asset server exists.
+#[derive(Asset, Resource, TypePath, Debug, Clone, Deserialize)]
+pub struct GameTuning {
+    pub rules: RulesTuning,
+    pub player: PlayerTuning,
+    pub enemy: EnemyTuning,
+    pub boost: BoostTuning,
+    pub health: HealthTuning,
+    pub combat: CombatTuning,
+    pub wave: WaveTuning,
+    pub sudden_death: SuddenDeathTuning,
+    pub tilt: TiltTuning,
+}
+
+impl Default for GameTuning {
+    fn default() -> Self {
+        Self {
+            rules: RulesTuning {
+                starting_lives: 3,
+                knockout_points: 2,
+                streak_bonus: 1,
+            },
+            player: PlayerTuning {
+                movement_force: 25.0,
+                movement_acceleration: 1.2,
+                boost_acceleration: 5.0,
+                max_speed: 8.0,
+                boost_max_speed: 18.0,
+                idle_deceleration_rate: 6.3,
+                fall_acceleration: 30.0,
+                turn_rate: 720.0,
+            },
+            enemy: EnemyTuning {
+                movement_force: 25.0,
+                max_speed: 12.0,
+                boost_acceleration: 5.0,
+                boost_max_speed: 18.0,
+                friction_rate: 1.83,
+                fall_acceleration: 30.0,
+                turn_rate: 540.0,
+                think_interval: 0.25,
+                target_interval: 1.0,
+                respawn_delay: 2.0,
+                switch_margin: 0.15,
+                edge_caution_start: 0.6,
+                powerup_range: 8.0,
+                ram_range: 6.0,
+                ram_aim_tolerance: 20.0,
+                ram_overrun: 3.0,
+                steering_response: 8.0,
+                arrival_radius: 3.0,
+                separation_radius: 2.0,
+                separation_weight: 0.8,
+                edge_margin: 2.5,
+                edge_lookahead: 0.5,
+                edge_weight: 1.5,
+                max_prediction: 1.0,
+            },
+            boost: BoostTuning {
+                energy_consumption_rate: 0.4,
+                recharge_rate: 0.5,
+                min_energy_to_start: 0.1,
+                cooldown: 1.0,
+                recharge_delay: 2.0,
+                windup: 0.15,
+                windup_brake: 6.0,
+            },
+            health: HealthTuning {
+                player_health: 100.0,
+                enemy_min_health: 75.0,
+                enemy_max_health: 150.0,
+                hit_damage: 4.0,
+                damage_per_speed: 1.5,
+                ram_damage_multiplier: 2.5,
+                recovery_rate: 5.0,
+                recovery_delay: 2.0,
+            },
+            combat: CombatTuning {
+                min_hit_speed: 2.0,
+                push_force: 3.0,
+                push_per_speed: 0.4,
+                ram_push_multiplier: 1.6,
+                weak_push_bonus: 1.5,
+                hit_stun: 0.25,
+                ram_hit_stun: 0.6,
+                attribution_window: 3.0,
+            },
+            wave: WaveTuning {
+                first_delay: 1.0,
+                breather: 4.0,
+                telegraph: 1.5,
+                waves: vec![
+                    WaveDefinition { enemies: 3, lives: 1, health_multiplier: 0.8 },
+                    WaveDefinition { enemies: 4, lives: 1, health_multiplier: 1.0 },
+                    WaveDefinition { enemies: 5, lives: 2, health_multiplier: 1.0 },
+                    WaveDefinition { enemies: 6, lives: 2, health_multiplier: 1.2 },
+                    WaveDefinition { enemies: 8, lives: 3, health_multiplier: 1.4 },
+                ],
+            },
+            sudden_death: SuddenDeathTuning {
+                start_after: 90.0,
+                stalemate_ducks: 2,
+                stage_interval: 20.0,
+                telegraph: 3.0,
+                shrink_time: 1.5,
+                shrink_per_stage: 0.15,
+                min_scale: 0.35,
+            },
+            tilt: TiltTuning {
+                weight_torque: 0.12,
+                stiffness: 8.0,
+                damping: 3.0,
+                max_angle: 15.0,
+                slide_acceleration: 15.0,
+            },
+        }
+    }
+}
+
+impl EnemyTuning {
+    /// This tuning with `difficulty`'s AI modifiers applied. Difficulty only
+    /// touches enemy values, so the rest of `GameTuning` is used as is.
+    pub fn for_difficulty(&self, difficulty: Difficulty) -> Self {
+        let modifiers = difficulty.modifiers();
+        Self {
+            think_interval: self.think_interval * modifiers.reaction_time,
+            target_interval: self.target_interval * modifiers.reaction_time,
+            max_speed: self.max_speed * modifiers.max_speed,
+            boost_max_speed: self.boost_max_speed * modifiers.max_speed,
+            max_prediction: self.max_prediction * modifiers.prediction,
+            ram_range: self.ram_range * modifiers.boost_usage,
+            ..self.clone()
+        }
+    }
+}
+
+impl GameTuning {
+    /// Checks the values that would panic or break a match mid-game: negative
+    /// timer lengths, zero-length repeating timers, fractions outside `0..=1`
+    /// and a wave list with nothing in it.
+    pub fn validate(&self) -> Result<(), String> {
+        let repeating = [
+            ("enemy.think_interval", self.enemy.think_interval),
+            ("enemy.target_interval", self.enemy.target_interval),
+        ];
+        let durations = [
+            ("enemy.respawn_delay", self.enemy.respawn_delay),
+            ("boost.cooldown", self.boost.cooldown),
+            ("boost.recharge_delay", self.boost.recharge_delay),
+            ("boost.windup", self.boost.windup),
+            ("health.recovery_delay", self.health.recovery_delay),
+            ("combat.hit_stun", self.combat.hit_stun),
+            ("combat.ram_hit_stun", self.combat.ram_hit_stun),
+            ("combat.attribution_window", self.combat.attribution_window),
+            ("wave.first_delay", self.wave.first_delay),
+            ("wave.breather", self.wave.breather),
+            ("wave.telegraph", self.wave.telegraph),
+            ("sudden_death.start_after", self.sudden_death.start_after),
+            ("sudden_death.stage_interval", self.sudden_death.stage_interval),
+            ("sudden_death.telegraph", self.sudden_death.telegraph),
+            ("sudden_death.shrink_time", self.sudden_death.shrink_time),
+        ];
+        let fractions = [
+            ("enemy.edge_caution_start", self.enemy.edge_caution_start),
+            ("boost.min_energy_to_start", self.boost.min_energy_to_start),
+            ("sudden_death.shrink_per_stage", self.sudden_death.shrink_per_stage),
+            ("sudden_death.min_scale", self.sudden_death.min_scale),
+        ];
+
+        for (name, seconds) in repeating {
+            if !(seconds.is_finite() && seconds > 0.0) {
+                return Err(format!("{} must be more than 0 seconds, got {}", name, seconds));
+            }
+        }
+        for (name, seconds) in durations {
+            if !(seconds.is_finite() && seconds >= 0.0) {
+                return Err(format!("{} must be 0 seconds or more, got {}", name, seconds));
+            }
+        }
+        for (name, fraction) in fractions {
+            if !(0.0..=1.0).contains(&fraction) {
+                return Err(format!("{} must be between 0 and 1, got {}", name, fraction));
+            }
+        }
+        if self.health.enemy_min_health > self.health.enemy_max_health {
+            return Err(format!(
+                "health.enemy_min_health ({}) is above health.enemy_max_health ({})",
+                self.health.enemy_min_health, self.health.enemy_max_health
+            ));
+        }
+        if self.wave.waves.is_empty() {
+            return Err("wave.waves must list at least one wave".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct GameTuningLoader;
+
+impl AssetLoader for GameTuningLoader {
+    type Asset = GameTuning;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<GameTuning, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            // Rejecting a bad edit keeps the last good tuning in play
+            let tuning = ron::de::from_bytes::<GameTuning>(&bytes)?;
+            tuning.validate()?;
+            Ok(tuning)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tuning.ron"]
+    }
+}
+
+/// Handle keeping the tuning asset alive so edits keep being picked up.
+#[derive(Resource)]
+pub struct GameTuningHandle(pub Handle<GameTuning>);
+
+pub struct TuningPlugin;
+
+impl Plugin for TuningPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameTuning>()
+            .init_asset::<GameTuning>()
+            .init_asset_loader::<GameTuningLoader>()
+            .add_systems(Startup, load_tuning)
+            .add_systems(Update, (
+                apply_tuning_asset,
+                apply_tuning_to_timers
+                    .run_if(resource_changed::<GameTuning>().or_else(resource_changed::<GameSettings>())),
+            ).chain());
+    }
+}
+
+fn load_tuning(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(GameTuningHandle(asset_server.load(TUNING_PATH)));
+}
+
+/// Run condition: the tuning file has finished loading (or failed to), so a
+/// match started now plays with the same constants as every other run.
+pub fn tuning_ready(asset_server: Res<AssetServer>, tuning: Option<Res<GameTuningHandle>>) -> bool {
+    tuning.map_or(true, |handle| {
+        matches!(
+            asset_server.get_load_state(&handle.0),
+            Some(LoadState::Loaded) | Some(LoadState::Failed) | None
+        )
+    })
+}
+
+/// Copies the tuning asset into the `GameTuning` resource whenever it (re)loads.
+fn apply_tuning_asset(
+    mut events: EventReader<AssetEvent<GameTuning>>,
+    handle: Option<Res<GameTuningHandle>>,
+    assets: Res<Assets<GameTuning>>,
+    mut tuning: ResMut<GameTuning>,
+) {
+    let Some(handle) = handle else {
+        return;
+    };
+
+    for event in events.read() {
+        if event.is_loaded_with_dependencies(&handle.0) || event.is_modified(&handle.0) {
+            if let Some(loaded) = assets.get(&handle.0) {
+                *tuning = loaded.clone();
+                info!("Applied gameplay tuning from {}", TUNING_PATH);
+            }
+        }
+    }
+}
+
+/// Pushes reloaded timer lengths onto ducks that are already in play.
+fn apply_tuning_to_timers(
+    tuning: Res<GameTuning>,
+    settings: Res<GameSettings>,
+    mut enemy_query: Query<&mut Enemy>,
+    mut boost_query: Query<&mut EnergyBoost>,
+    mut health_query: Query<&mut Health>,
+) {
+    let enemy_tuning = tuning.enemy.for_difficulty(settings.difficulty);
+    for mut enemy in enemy_query.iter_mut() {
+        enemy.state_timer.set_duration(Duration::from_secs_f32(enemy_tuning.think_interval));
+        enemy.target_timer.set_duration(Duration::from_secs_f32(enemy_tuning.target_interval));
+        enemy.respawn_timer.set_duration(Duration::from_secs_f32(enemy_tuning.respawn_delay));
+    }
+
+    for mut boost in boost_query.iter_mut() {
+        boost.cooldown_timer.set_duration(Duration::from_secs_f32(tuning.boost.cooldown));
+        boost.recharge_timer.set_duration(Duration::from_secs_f32(tuning.boost.recharge_delay));
+        boost.windup_timer.set_duration(Duration::from_secs_f32(tuning.boost.windup));
+    }
+
+    for mut health in health_query.iter_mut() {
+        health.recovery_timer.set_duration(Duration::from_secs_f32(tuning.health.recovery_delay));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_tuning_is_valid() {
+        let shipped: GameTuning =
+            ron::from_str(include_str!("../../assets/tuning/game.tuning.ron")).expect("tuning file parses");
+        assert_eq!(shipped.validate(), Ok(()));
+        assert_eq!(GameTuning::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn out_of_range_values_are_rejected() {
+        let mut tuning = GameTuning::default();
+        tuning.enemy.edge_caution_start = 1.5;
+        assert!(tuning.validate().unwrap_err().contains("enemy.edge_caution_start"));
+
+        let mut tuning = GameTuning::default();
+        tuning.boost.cooldown = -1.0;
+        assert!(tuning.validate().unwrap_err().contains("boost.cooldown"));
+
+        let mut tuning = GameTuning::default();
+        tuning.enemy.think_interval = 0.0;
+        assert!(tuning.validate().unwrap_err().contains("enemy.think_interval"));
+
+        let mut tuning = GameTuning::default();
+        tuning.wave.waves.clear();
+        assert!(tuning.validate().unwrap_err().contains("wave.waves"));
+    }
+}
@@ -6,6 +6,7 @@ pub enum GameState {
     MainMenu,
     InGame,
     Settings,
+    HighScores,
     GameOver,
     WinScreen,
     Paused,
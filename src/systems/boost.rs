@@ -1,100 +1,176 @@
 use bevy::prelude::*;
-use rand::Rng;
+use bevy_rapier3d::prelude::Velocity;
 
-use crate::components::{EnergyBoost, Player, Enemy, BoostIndicator};
-
-const BOOST_THRESHOLD: f32 = 0.95;
-const ENERGY_CONSUMPTION_RATE: f32 = 0.4;  // Energy consumed per second while boosting
+use crate::components::{
+    EnergyBoost, Player, PlayerInput, Enemy, EnemyState, BoostIndicator, GameSettings, HitStun,
+};
+use crate::resources::{Arena, BoostTuning, GameTuning, SoundEffect};
+use crate::systems::facing;
 
+/// Players boost along their input direction, or the way they face when
+/// there is no input; the direction is locked for the whole boost.
 pub fn handle_boost(
-    mut query: Query<(&mut EnergyBoost, &Player)>,
-    keyboard: Res<Input<KeyCode>>,
+    mut query: Query<(&mut EnergyBoost, &PlayerInput, &Transform, &HitStun), With<Player>>,
     time: Res<Time>,
+    tuning: Res<GameTuning>,
+    mut sounds: EventWriter<SoundEffect>,
 ) {
-    for (mut boost, _) in query.iter_mut() {
-        // Handle cooldown
-        if boost.cooldown_timer.tick(time.delta()).finished() && boost.is_boosting {
-            boost.is_boosting = false;
-        }
+    let tuning = &tuning.boost;
+    for (mut boost, input, transform, stun) in query.iter_mut() {
+        let was_boosting = boost.is_boosting;
 
-        // Gradually consume energy while boosting
-        if boost.is_boosting {
-            // Consume energy over time
-            boost.energy = (boost.energy - time.delta_seconds() * ENERGY_CONSUMPTION_RATE).max(0.0);
-            
-            // Stop boosting if energy is depleted
-            if boost.energy <= 0.0 {
-                boost.is_boosting = false;
-                boost.cooldown_timer.reset();
-                boost.recharge_timer.reset();
-            }
-        }
-        // Handle recharge when not boosting
-        else if boost.recharge_timer.tick(time.delta()).finished() {
-            boost.energy = (boost.energy + time.delta_seconds() * 0.5).min(1.0);
-        }
+        update_boost_energy(&mut boost, tuning, &time);
 
-        // Start boosting when space is pressed
-        if keyboard.just_pressed(KeyCode::Space) && boost.energy > 0.1 && !boost.is_boosting {
-            apply_boost(&mut boost);
+        // Start boosting when the Boost action is pressed
+        if input.boost_pressed
+            && boost.energy > tuning.min_energy_to_start
+            && !boost.is_boosting
+            && !stun.is_stunned()
+        {
+            let direction = Vec3::new(input.movement.x, 0.0, -input.movement.y)
+                .try_normalize()
+                .unwrap_or_else(|| facing(transform));
+            apply_boost(&mut boost, direction);
         }
         
-        // Stop boosting when space is released
-        if keyboard.just_released(KeyCode::Space) && boost.is_boosting {
-            boost.is_boosting = false;
-            boost.cooldown_timer.reset();
+        // Stop boosting when the Boost action is released
+        if input.boost_released && boost.is_boosting {
+            stop_boost(&mut boost);
         }
+
+        send_boost_sound(&mut sounds, was_boosting, boost.is_boosting);
     }
 }
 
+/// Enemies boost only to ram: once they are lined up with their target
+/// inside ram range, and only when the ram won't carry them off the edge.
+/// A ram is called off when the enemy changes its mind or its course starts
+/// to run off the platform.
 pub fn handle_ai_boost(
-    mut query: Query<(&mut EnergyBoost, &Enemy)>,
+    mut query: Query<(&mut EnergyBoost, &Enemy, &Transform, &Velocity, &HitStun)>,
+    arena: Res<Arena>,
     time: Res<Time>,
+    tuning: Res<GameTuning>,
+    settings: Res<GameSettings>,
+    mut sounds: EventWriter<SoundEffect>,
 ) {
-    let mut rng = rand::thread_rng();
-    
-    for (mut boost, _) in query.iter_mut() {
-        // Handle cooldown
-        if boost.cooldown_timer.tick(time.delta()).finished() && boost.is_boosting {
-            boost.is_boosting = false;
-        }
+    let enemy_tuning = tuning.enemy.for_difficulty(settings.difficulty);
+    let safe = |point: Vec2| arena.edge_distance(point) >= enemy_tuning.edge_margin;
+
+    for (mut boost, enemy, transform, velocity, stun) in query.iter_mut() {
+        let was_boosting = boost.is_boosting;
+
+        update_boost_energy(&mut boost, &tuning.boost, &time);
 
-        // Gradually consume energy while boosting
-        if boost.is_boosting {
-            // Consume energy over time
-            boost.energy = (boost.energy - time.delta_seconds() * ENERGY_CONSUMPTION_RATE).max(0.0);
-            
-            // Stop boosting if energy is depleted
-            if boost.energy <= 0.0 {
-                boost.is_boosting = false;
-                boost.cooldown_timer.reset();
-                boost.recharge_timer.reset();
+        let position = transform.translation.xz();
+        let heading = velocity.linvel.xz();
+        if boost.is_pushing() {
+            let ahead = position + heading * enemy_tuning.edge_lookahead;
+            if enemy.state != EnemyState::Ram || enemy.is_fallen || !safe(ahead) {
+                stop_boost(&mut boost);
+            }
+        } else if boost.is_boosting {
+            if enemy.state != EnemyState::Ram || enemy.is_fallen {
+                stop_boost(&mut boost);
+            }
+        } else if enemy.state == EnemyState::Ram
+            && !enemy.is_fallen
+            && !stun.is_stunned()
+            && boost.energy > tuning.boost.min_energy_to_start
+        {
+            if let Some(aim) = enemy.target_position.map(|aim| aim.xz()) {
+                let to_target = aim - position;
+                let distance = to_target.length();
+                let lined_up = heading.length() > f32::EPSILON
+                    && heading.normalize().dot(to_target.normalize_or_zero())
+                        >= enemy_tuning.ram_aim_tolerance.to_radians().cos();
+                let landing = position + to_target.normalize_or_zero() * (distance + enemy_tuning.ram_overrun);
+                if distance <= enemy_tuning.ram_range && lined_up && safe(landing) {
+                    apply_boost(&mut boost, Vec3::new(to_target.x, 0.0, to_target.y).normalize_or_zero());
+                }
             }
         }
-        // Handle recharge when not boosting
-        else if boost.recharge_timer.tick(time.delta()).finished() {
-            boost.energy = (boost.energy + time.delta_seconds() * 0.5).min(1.0);
-        }
 
-        // Random chance to boost if energy is high enough
-        if boost.energy > BOOST_THRESHOLD && !boost.is_boosting && rng.gen_bool(0.1) {
-            apply_boost(&mut boost);
+        send_boost_sound(&mut sounds, was_boosting, boost.is_boosting);
+    }
+}
+
+/// Drains energy while boosting and recharges it after the recharge delay
+/// otherwise. Boosts end when the cooldown runs out or the energy does.
+/// Shared by players and enemies so both play by the same rules.
+fn update_boost_energy(boost: &mut EnergyBoost, tuning: &BoostTuning, time: &Time) {
+    // Nothing is spent while the boost is still being telegraphed
+    if boost.is_winding_up() {
+        boost.windup_timer.tick(time.delta());
+        return;
+    }
+
+    // Handle cooldown
+    if boost.cooldown_timer.tick(time.delta()).finished() && boost.is_boosting {
+        boost.is_boosting = false;
+    }
+
+    // Gradually consume energy while boosting
+    if boost.is_boosting {
+        // Consume energy over time
+        boost.energy = (boost.energy - time.delta_seconds() * tuning.energy_consumption_rate).max(0.0);
+
+        // Stop boosting if energy is depleted
+        if boost.energy <= 0.0 {
+            boost.is_boosting = false;
+            boost.cooldown_timer.reset();
+            boost.recharge_timer.reset();
         }
     }
+    // Handle recharge when not boosting
+    else if boost.recharge_timer.tick(time.delta()).finished() {
+        boost.energy = (boost.energy + time.delta_seconds() * tuning.recharge_rate).min(1.0);
+    }
+}
+
+/// Cues a boost sound when a duck starts or stops boosting this tick.
+fn send_boost_sound(sounds: &mut EventWriter<SoundEffect>, was_boosting: bool, is_boosting: bool) {
+    match (was_boosting, is_boosting) {
+        (false, true) => sounds.send(SoundEffect::BoostStart),
+        (true, false) => sounds.send(SoundEffect::BoostStop),
+        _ => {}
+    }
 }
 
-fn apply_boost(boost: &mut EnergyBoost) {
+fn apply_boost(boost: &mut EnergyBoost, direction: Vec3) {
     // Just set the boosting flag, energy will be consumed gradually
     boost.is_boosting = true;
+    boost.direction = direction;
+    boost.cooldown_timer.reset();
+    boost.windup_timer.reset();
+}
+
+fn stop_boost(boost: &mut EnergyBoost) {
+    boost.is_boosting = false;
     boost.cooldown_timer.reset();
 }
 
+/// Telegraphs a boost that is winding up with a ring under the duck and a
+/// line along the locked direction.
+pub fn draw_boost_telegraph(query: Query<(&EnergyBoost, &Transform)>, mut gizmos: Gizmos) {
+    for (boost, transform) in query.iter() {
+        if !boost.is_winding_up() {
+            continue;
+        }
+        let ground = transform.translation - Vec3::Y * 0.4;
+        let progress = boost.windup_timer.percent();
+        gizmos.circle(ground, Vec3::Y, 1.2 - 0.6 * progress, Color::ORANGE_RED);
+        gizmos.line(ground, ground + boost.direction * (1.0 + 3.0 * progress), Color::ORANGE_RED);
+    }
+}
+
+/// Sizes each player's HUD boost bar to their remaining energy.
 pub fn update_boost_indicator(
     query: Query<(&EnergyBoost, &Player)>,
-    mut indicator_query: Query<&mut Style, With<BoostIndicator>>,
+    mut indicator_query: Query<(&mut Style, &BoostIndicator)>,
 ) {
-    if let Ok((boost, _)) = query.get_single() {
-        if let Ok(mut style) = indicator_query.get_single_mut() {
+    for (mut style, indicator) in indicator_query.iter_mut() {
+        if let Some((boost, _)) = query.iter().find(|(_, player)| player.index == indicator.player) {
             style.width = Val::Percent(boost.energy * 100.0);
         }
     }
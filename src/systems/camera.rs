@@ -1,60 +1,60 @@
 use bevy::prelude::*;
-use crate::components::{Player, Enemy};
-
+use crate::components::{Contestant, Enemy, Player};
+use crate::resources::Arena;
+
+/// How far below the platform players are still framed.
+const PLATFORM_TOLERANCE: f32 = 2.0;
+/// Exponential approach rate (1/s); lower values make a lazier camera.
+const CAMERA_SMOOTHING: f32 = 3.0;
+/// Spread of the players' bounds the camera always leaves room for.
+const MIN_FRAMED_SPREAD: f32 = 6.0;
+/// Camera height and distance behind the focus, per unit of spread.
+const HEIGHT_PER_SPREAD: f32 = 1.1;
+const DISTANCE_PER_SPREAD: f32 = 1.4;
+/// Height and distance added on top, so a lone duck isn't filmed up close.
+const BASE_HEIGHT: f32 = 8.0;
+const BASE_DISTANCE: f32 = 10.0;
+
+/// Keeps every living human duck in view: the camera looks at the middle of
+/// their bounds and backs off as they spread out. Enemies are not framed.
 pub fn update_camera_position(
     mut camera_query: Query<&mut Transform, (With<Camera>, Without<Player>, Without<Enemy>)>,
-    entity_query: Query<&GlobalTransform, Or<(With<Player>, With<Enemy>)>>,
+    player_query: Query<(&GlobalTransform, &Contestant), With<Player>>,
+    arena: Res<Arena>,
     time: Res<Time>,
+    mut focus: Local<Option<Vec3>>,
 ) {
-    // Platform height where entities should be considered
-    const PLATFORM_HEIGHT: f32 = 5.0;
-    const PLATFORM_TOLERANCE: f32 = 2.0;
-    const CAMERA_SMOOTHING: f32 = 0.001; // Lower value means slower camera movement
-
-    // If no camera or no entities, do nothing
-    if camera_query.is_empty() || entity_query.is_empty() {
+    let Ok(mut camera_transform) = camera_query.get_single_mut() else {
         return;
+    };
+
+    // Players that are out of lives or already falling away are left behind
+    let mut bounds: Option<(Vec2, Vec2)> = None;
+    for (transform, contestant) in player_query.iter() {
+        let position = transform.translation();
+        if contestant.is_eliminated() || arena.height_above_platform(position) < -PLATFORM_TOLERANCE {
+            continue;
+        }
+        let point = position.xz();
+        bounds = Some(match bounds {
+            Some((min, max)) => (min.min(point), max.max(point)),
+            None => (point, point),
+        });
     }
-
-    // Filter out entities that are falling (below platform height)
-    let active_entity_positions: Vec<Vec3> = entity_query.iter()
-        .map(|transform| transform.translation())
-        .filter(|pos| pos.y >= PLATFORM_HEIGHT - PLATFORM_TOLERANCE)
-        .collect();
-
-    // If no active entities, do nothing
-    if active_entity_positions.is_empty() {
+    let Some((min, max)) = bounds else {
         return;
-    }
-
-    // Calculate the center point of active entities
-    let center = active_entity_positions.iter().sum::<Vec3>() / active_entity_positions.len() as f32;
-
-    // Calculate the maximum distance from the center to determine camera zoom/height
-    let max_distance = active_entity_positions
-        .iter()
-        .map(|&pos| Vec3::distance(pos, center))
-        .max_by(|a, b| a.partial_cmp(b).unwrap())
-        .unwrap_or(10.0);
-
-    // Adjust camera position based on entities' positions
-    // Raise the camera height to provide a good overview
-    let camera_height = max_distance.max(10.0) + 10.0;
-    let camera_distance = max_distance.max(10.0) + 15.0;
-
-    if let Ok(mut camera_transform) = camera_query.get_single_mut() {
-        // Smoothly interpolate camera position
-        let target_translation = Vec3::new(
-            center.x, 
-            camera_height, 
-            center.z + camera_distance
-        );
-        
-        camera_transform.translation = camera_transform.translation.lerp(
-            target_translation, 
-            time.delta_seconds() * CAMERA_SMOOTHING
-        );
-        
-        camera_transform.look_at(center, Vec3::Y);
-    }
+    };
+
+    let middle = (min + max) / 2.0;
+    let spread = ((max - min).length() / 2.0).max(MIN_FRAMED_SPREAD);
+    let target_focus = Vec3::new(middle.x, arena.level_at(middle), middle.y);
+    let target_translation = target_focus
+        + Vec3::new(0.0, BASE_HEIGHT + spread * HEIGHT_PER_SPREAD, BASE_DISTANCE + spread * DISTANCE_PER_SPREAD);
+
+    // Frame-rate independent smoothing of both where the camera is and where it looks
+    let blend = 1.0 - (-CAMERA_SMOOTHING * time.delta_seconds()).exp();
+    let current_focus = focus.map_or(target_focus, |current| current.lerp(target_focus, blend));
+    *focus = Some(current_focus);
+    camera_transform.translation = camera_transform.translation.lerp(target_translation, blend);
+    camera_transform.look_at(current_focus, Vec3::Y);
 }
@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::components::{
+    Contestant, DuckKnockedOut, DuckPushed, EnergyBoost, Health, HitStun, LastHitBy,
+};
+use crate::resources::{GameTuning, SoundEffect};
+
+/// Turns fast contacts between ducks into shoves: both ducks lose health
+/// and are knocked away from each other, harder the faster they closed.
+/// Being rammed by a boosting duck hurts and shoves more, big ducks shove
+/// small ones around, and weak ducks fly further. Knocked-back ducks are
+/// stunned for a moment, except a rammer that hit a duck that wasn't
+/// boosting itself.
+pub fn resolve_duck_hits(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut duck_query: Query<(Entity, &Transform, &mut Velocity, &mut Health, &mut HitStun, &EnergyBoost)>,
+    tuning: Res<GameTuning>,
+    mut pushes: EventWriter<DuckPushed>,
+    mut sounds: EventWriter<SoundEffect>,
+) {
+    for collision_event in collision_events.read() {
+        let CollisionEvent::Started(e1, e2, _) = collision_event else {
+            continue;
+        };
+        let Ok([mut a, mut b]) = duck_query.get_many_mut([*e1, *e2]) else {
+            continue;
+        };
+
+        // Direction from a to b on the platform plane
+        let normal = ((b.1.translation - a.1.translation) * Vec3::new(1.0, 0.0, 1.0)).normalize_or_zero();
+        let closing_speed = (a.2.linvel - b.2.linvel).dot(normal);
+        if normal == Vec3::ZERO || closing_speed < tuning.combat.min_hit_speed {
+            continue;
+        }
+
+        let (a_entity, b_entity) = (a.0, b.0);
+        let a_ramming = a.5.is_pushing();
+        let b_ramming = b.5.is_pushing();
+        let a_size = a.1.scale.x.max(0.1);
+        let b_size = b.1.scale.x.max(0.1);
+
+        let damage = tuning.health.hit_damage + closing_speed * tuning.health.damage_per_speed;
+        let push = tuning.combat.push_force + closing_speed * tuning.combat.push_per_speed;
+        for (duck, pusher, away, rammed, ramming, size_ratio) in [
+            (&mut a, b_entity, -normal, b_ramming, a_ramming, b_size / a_size),
+            (&mut b, a_entity, normal, a_ramming, b_ramming, a_size / b_size),
+        ] {
+            let (ram_damage, ram_push, stun) = if rammed {
+                (tuning.health.ram_damage_multiplier, tuning.combat.ram_push_multiplier, tuning.combat.ram_hit_stun)
+            } else {
+                (1.0, 1.0, tuning.combat.hit_stun)
+            };
+            duck.3.take_damage(damage * ram_damage);
+
+            let weakness = 1.0 - duck.3.fraction();
+            let impulse = away * push * ram_push * size_ratio * (1.0 + tuning.combat.weak_push_bonus * weakness);
+            duck.2.linvel += impulse;
+            if !ramming || rammed {
+                duck.4.stun(stun);
+            }
+
+            pushes.send(DuckPushed {
+                pushed: duck.0,
+                pusher,
+                impulse,
+                closing_speed,
+                rammed,
+            });
+        }
+        sounds.send(SoundEffect::Hit);
+    }
+}
+
+/// Counts down hit-stun.
+pub fn tick_hit_stun(mut stun_query: Query<&mut HitStun>, time: Res<Time>) {
+    for mut stun in stun_query.iter_mut() {
+        stun.timer.tick(time.delta());
+    }
+}
+
+/// Remembers who last shoved each duck, for the attribution window.
+pub fn track_last_hitter(
+    mut pushes: EventReader<DuckPushed>,
+    mut hit_query: Query<&mut LastHitBy>,
+    time: Res<Time>,
+    tuning: Res<GameTuning>,
+) {
+    for mut last_hit in hit_query.iter_mut() {
+        last_hit.timer.tick(time.delta());
+    }
+
+    for push in pushes.read() {
+        if let Ok(mut last_hit) = hit_query.get_mut(push.pushed) {
+            last_hit.record(push.pusher, tuning.combat.attribution_window);
+        }
+    }
+}
+
+/// Gives the attacker points and streak credit for every knockout.
+pub fn award_knockouts(
+    mut knockouts: EventReader<DuckKnockedOut>,
+    mut contestant_query: Query<&mut Contestant>,
+    tuning: Res<GameTuning>,
+) {
+    for knockout in knockouts.read() {
+        let eliminated = contestant_query
+            .get(knockout.victim)
+            .is_ok_and(|victim| victim.is_eliminated());
+        if let Ok(mut attacker) = contestant_query.get_mut(knockout.attacker) {
+            attacker.record_knockout(tuning.rules.knockout_points, tuning.rules.streak_bonus, eliminated);
+            debug!("{} knocked out {:?}, streak {}", attacker.name, knockout.victim, attacker.streak);
+        }
+    }
+}
+
+/// Seconds an impact ring stays on screen.
+const IMPACT_RING_LIFETIME: f32 = 0.3;
+
+/// A shove still being drawn.
+pub struct ImpactRing {
+    position: Vec3,
+    impulse: Vec3,
+    rammed: bool,
+    age: f32,
+}
+
+/// Flashes an expanding ring where a duck was shoved, pointing the way it
+/// was knocked; rams flash red.
+pub fn draw_impact_rings(
+    mut pushes: EventReader<DuckPushed>,
+    duck_query: Query<&Transform>,
+    time: Res<Time>,
+    mut rings: Local<Vec<ImpactRing>>,
+    mut gizmos: Gizmos,
+) {
+    for push in pushes.read() {
+        debug!("{:?} shoved {:?} at closing speed {:.1}", push.pusher, push.pushed, push.closing_speed);
+        if let Ok(transform) = duck_query.get(push.pushed) {
+            rings.push(ImpactRing {
+                position: transform.translation,
+                impulse: push.impulse,
+                rammed: push.rammed,
+                age: 0.0,
+            });
+        }
+    }
+
+    rings.retain_mut(|ring| {
+        ring.age += time.delta_seconds();
+        ring.age < IMPACT_RING_LIFETIME
+    });
+    for ring in rings.iter() {
+        let progress = ring.age / IMPACT_RING_LIFETIME;
+        let color = if ring.rammed { Color::RED } else { Color::WHITE };
+        gizmos.circle(ring.position, Vec3::Y, 0.5 + progress * ring.impulse.length() * 0.2, color);
+        gizmos.line(ring.position, ring.position + ring.impulse * 0.2 * (1.0 - progress), color);
+    }
+}
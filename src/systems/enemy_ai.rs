@@ -0,0 +1,262 @@
+use bevy::prelude::*;
+
+use crate::components::{
+    AiDecision, Contestant, Enemy, EnemyState, EnergyBoost, GameSettings, Health, Personality,
+    Player,
+};
+use crate::resources::{Arena, EnemyTuning, GameRng, GameTuning, RngStream};
+use crate::systems::PowerUpCoin;
+
+/// How far from the safest spot, as a fraction of a random platform spot, a
+/// recovering enemy settles.
+const RECOVER_SPREAD: f32 = 0.25;
+
+/// A duck the utility AI could go after.
+struct Candidate {
+    entity: Entity,
+    position: Vec3,
+    /// 0 at full health, 1 with none left
+    weakness: f32,
+}
+
+/// Everything one enemy's scores are computed from.
+struct Situation<'a> {
+    position: Vec3,
+    energy: f32,
+    /// 0 while safely inside, rising to 1 at the platform edge
+    edge_danger: f32,
+    /// 0 at the safest spot of the platform, 1 at the edge
+    off_center: f32,
+    target: Option<&'a Candidate>,
+    coin: Option<(Vec3, f32)>,
+}
+
+/// How worth chasing `candidate` is: near ducks for most enemies, weak
+/// ducks for the bullies.
+fn appeal(position: Vec3, reach: f32, personality: &Personality, candidate: &Candidate) -> f32 {
+    let closeness = 1.0 - (candidate.position.distance(position) / reach).min(1.0);
+    closeness + (candidate.weakness - closeness) * personality.bullying
+}
+
+/// The duck most worth chasing.
+fn pick_target<'a>(
+    position: Vec3,
+    reach: f32,
+    personality: &Personality,
+    candidates: impl Iterator<Item = &'a Candidate>,
+) -> Option<(&'a Candidate, f32)> {
+    candidates
+        .map(|candidate| (candidate, appeal(position, reach, personality, candidate)))
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+}
+
+/// Unweighted utility of `option` in `situation`, roughly in 0..=1.
+fn score(option: EnemyState, situation: &Situation, target_appeal: f32, tuning: &EnemyTuning, min_energy: f32) -> f32 {
+    let safety = 1.0 - situation.edge_danger;
+    match option {
+        EnemyState::Chase => match situation.target {
+            Some(_) => (0.4 + 0.6 * target_appeal) * (0.5 + 0.5 * safety),
+            None => 0.0,
+        },
+        EnemyState::Retreat => 1.5 * situation.edge_danger * situation.edge_danger,
+        EnemyState::GrabPowerUp => match situation.coin {
+            Some((_, distance)) => 0.9 * (1.0 - distance / tuning.powerup_range),
+            None => 0.0,
+        },
+        EnemyState::Ram => match situation.target {
+            Some(target) => {
+                let distance = target.position.distance(situation.position);
+                if distance > tuning.ram_range || situation.energy < min_energy
+                {
+                    0.0
+                } else {
+                    // Weak ducks fly further when rammed
+                    (1.0 - distance / tuning.ram_range)
+                        * situation.energy
+                        * safety
+                        * (0.7 + 0.3 * target.weakness)
+                }
+            }
+            None => 0.0,
+        },
+        EnemyState::Recover => {
+            0.2 + 0.3 * situation.off_center.min(1.0)
+        }
+        EnemyState::Fallen => 0.0,
+    }
+}
+
+/// Scores every option for each enemy on its think timer and switches to the
+/// best one, with a margin in favour of the current choice so enemies don't
+/// dither between two close options.
+///
+/// Decisions deliberately don't happen every tick: `Enemy::state_timer`
+/// repeats every `enemy.think_interval` seconds (scaled by the difficulty's
+/// reaction time, and updated on enemies in play when the tuning reloads),
+/// which gives enemies a reaction delay. Movement still steers every tick
+/// towards the goal chosen last. Targets are only re-picked every
+/// `enemy.target_interval` seconds via `Enemy::target_timer`, or as soon as
+/// the current one falls or is eliminated.
+pub fn enemy_decide(
+    mut enemy_query: Query<(
+        Entity,
+        &mut Enemy,
+        &mut AiDecision,
+        &Personality,
+        &Transform,
+        &EnergyBoost,
+        &Contestant,
+    )>,
+    duck_query: Query<(Entity, &Transform, &Contestant, &Health), Or<(With<Player>, With<Enemy>)>>,
+    coin_query: Query<&Transform, With<PowerUpCoin>>,
+    arena: Res<Arena>,
+    time: Res<Time>,
+    mut game_rng: ResMut<GameRng>,
+    tuning: Res<GameTuning>,
+    settings: Res<GameSettings>,
+) {
+    let min_energy = tuning.boost.min_energy_to_start;
+    let tuning = &tuning.enemy.for_difficulty(settings.difficulty);
+    let aggression = settings.difficulty.modifiers().aggression;
+    let rng = game_rng.stream(RngStream::EnemyAi);
+    let reach = 2.0 * arena.extent();
+
+    // Ducks standing on the platform; fallen and eliminated ones are out of reach
+    let ducks = duck_query
+        .iter()
+        .filter(|(_, transform, contestant, _)| {
+            !contestant.is_eliminated() && arena.is_standing(transform.translation)
+        })
+        .map(|(entity, transform, _, health)| Candidate {
+            entity,
+            position: transform.translation,
+            weakness: 1.0 - health.fraction(),
+        })
+        .collect::<Vec<_>>();
+
+    for (entity, mut enemy, mut decision, personality, transform, boost, contestant) in enemy_query.iter_mut() {
+        if enemy.is_fallen {
+            continue;
+        }
+        // Forgetting the target makes the next decision pick a fresh one
+        if enemy.target_timer.tick(time.delta()).just_finished() {
+            decision.target = None;
+        }
+        if !enemy.state_timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        // Stick with the current target until it is due for a rethink or out of reach
+        let position = transform.translation;
+        let others = ducks.iter().filter(|duck| duck.entity != entity);
+        let target = decision
+            .target
+            .and_then(|current| others.clone().find(|duck| duck.entity == current))
+            .map(|candidate| (candidate, appeal(position, reach, personality, candidate)))
+            .or_else(|| pick_target(position, reach, personality, others));
+        let coin = coin_query
+            .iter()
+            .map(|coin| (coin.translation, coin.translation.xz().distance(position.xz())))
+            .filter(|(_, distance)| *distance < tuning.powerup_range)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        let edge_start = tuning.edge_caution_start.clamp(0.0, 0.99);
+        let off_center = 1.0 - arena.edge_distance(position.xz()) / arena.depth();
+        let situation = Situation {
+            position,
+            energy: boost.energy,
+            edge_danger: ((off_center - edge_start) / (1.0 - edge_start)).clamp(0.0, 1.0),
+            off_center: off_center.max(0.0),
+            target: target.map(|(candidate, _)| candidate),
+            coin,
+        };
+        let target_appeal = target.map_or(0.0, |(_, appeal)| appeal);
+
+        decision.scores = EnemyState::OPTIONS
+            .iter()
+            .map(|&option| {
+                let utility = score(option, &situation, target_appeal, tuning, min_energy);
+                let drive = match option {
+                    EnemyState::Chase | EnemyState::Ram => aggression,
+                    _ => 1.0,
+                };
+                (option, utility * personality.weight(option) * drive)
+            })
+            .collect();
+        decision.target = situation.target.map(|candidate| candidate.entity);
+
+        let current = enemy.state;
+        let (best, _) = decision
+            .scores
+            .iter()
+            .map(|&(option, score)| {
+                let bias = if option == current { tuning.switch_margin } else { 0.0 };
+                (option, score + bias)
+            })
+            .fold((EnemyState::Recover, f32::MIN), |best, candidate| {
+                if candidate.1 > best.1 { candidate } else { best }
+            });
+
+        enemy.target = match best {
+            EnemyState::Chase | EnemyState::Ram => decision.target,
+            _ => None,
+        };
+        enemy.target_position = match best {
+            EnemyState::Chase | EnemyState::Ram => situation.target.map(|candidate| candidate.position),
+            EnemyState::GrabPowerUp => coin.map(|(coin_position, _)| coin_position),
+            EnemyState::Retreat => {
+                let home = arena.home(position.xz());
+                Some(Vec3::new(home.x, position.y, home.y))
+            }
+            EnemyState::Recover if best != current || enemy.target_position.is_none() => {
+                let home = arena.home(position.xz());
+                let spot = Enemy::get_random_platform_position(rng, &arena);
+                let settle = home + (spot.xz() - home) * RECOVER_SPREAD;
+                Some(Vec3::new(settle.x, spot.y, settle.y))
+            }
+            _ => enemy.target_position,
+        };
+
+        if best != current {
+            debug!("{} switches {:?} -> {:?}: {:?}", contestant.name, current, best, decision.scores);
+            enemy.state = best;
+        }
+    }
+}
+
+/// Toggles drawing of every enemy's current goal, for tuning the AI.
+#[derive(Resource, Default)]
+pub struct AiDebugOverlay(pub bool);
+
+pub fn toggle_ai_debug_overlay(keyboard: Res<Input<KeyCode>>, mut overlay: ResMut<AiDebugOverlay>) {
+    if keyboard.just_pressed(KeyCode::F3) {
+        overlay.0 = !overlay.0;
+    }
+}
+
+/// Draws a line from each enemy to its goal, colored by the option it chose.
+pub fn draw_ai_debug_overlay(
+    overlay: Res<AiDebugOverlay>,
+    enemy_query: Query<(&Enemy, &Transform)>,
+    mut gizmos: Gizmos,
+) {
+    if !overlay.0 {
+        return;
+    }
+
+    for (enemy, transform) in enemy_query.iter() {
+        let Some(goal) = enemy.target_position else {
+            continue;
+        };
+        let color = match enemy.state {
+            EnemyState::Chase => Color::ORANGE,
+            EnemyState::Retreat => Color::CYAN,
+            EnemyState::GrabPowerUp => Color::GOLD,
+            EnemyState::Ram => Color::RED,
+            EnemyState::Recover => Color::GREEN,
+            EnemyState::Fallen => Color::GRAY,
+        };
+        gizmos.line(transform.translation, goal, color);
+        gizmos.sphere(goal, Quat::IDENTITY, 0.3, color);
+    }
+}
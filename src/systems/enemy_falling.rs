@@ -1,43 +1,61 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::{Velocity};
 
-use crate::components::{Enemy, EnemyState, PLATFORM_HEIGHT, BearScore};
-
-const FALL_THRESHOLD: f32 = -5.0;
-const RESPAWN_POSITION: Vec3 = Vec3::new(0.0, PLATFORM_HEIGHT + 2.0, 0.0);
-const FALL_ACCELERATION: f32 = 30.0;  // Additional downward force when falling
+use crate::components::{
+    Enemy, EnemyState, Contestant, Health, LastHitBy, DuckKnockedOut,
+};
+use crate::resources::{Arena, GameTuning, SoundEffect};
 
 pub fn handle_enemy_falls(
-    mut enemy_query: Query<(&mut Enemy, &mut Transform, &mut Velocity, &mut BearScore)>,
+    mut enemy_query: Query<(
+        Entity,
+        &mut Enemy,
+        &mut Transform,
+        &mut Velocity,
+        &mut Contestant,
+        &mut Health,
+        &mut LastHitBy,
+    )>,
+    arena: Res<Arena>,
     time: Res<Time>,
+    tuning: Res<GameTuning>,
+    mut sounds: EventWriter<SoundEffect>,
+    mut knockouts: EventWriter<DuckKnockedOut>,
 ) {
-    for (mut enemy, mut transform, mut velocity, mut score) in enemy_query.iter_mut() {
+    for (entity, mut enemy, mut transform, mut velocity, mut contestant, mut health, mut last_hit) in
+        enemy_query.iter_mut()
+    {
         // Apply extra downward force when falling
-        if transform.translation.y < PLATFORM_HEIGHT && !enemy.is_fallen {
-            velocity.linvel.y -= FALL_ACCELERATION * time.delta_seconds();
+        if arena.height_above_platform(transform.translation) < 0.0 && !enemy.is_fallen {
+            velocity.linvel.y -= tuning.enemy.fall_acceleration * time.delta_seconds();
         }
 
         // Check if enemy has fallen
-        if transform.translation.y < FALL_THRESHOLD && !enemy.is_fallen {
+        if transform.translation.y < arena.kill_plane && !enemy.is_fallen {
             // Enemy has fallen
             enemy.is_fallen = true;
             enemy.state = EnemyState::Fallen;
             enemy.respawn_timer.reset();
-            score.value -= 1;  // Deduct a point
+            contestant.record_fall();
+            sounds.send(SoundEffect::Fall);
+            if let Some(attacker) = last_hit.take_credit().filter(|attacker| *attacker != entity) {
+                knockouts.send(DuckKnockedOut { victim: entity, attacker });
+            }
             
             // Keep the enemy at the bottom of the platform
-            transform.translation.y = FALL_THRESHOLD;
+            transform.translation.y = arena.kill_plane;
             velocity.linvel = Vec3::ZERO;
             velocity.angvel = Vec3::ZERO;
         }
 
-        // Handle respawn timer for fallen enemies
-        if enemy.is_fallen {
+        // Handle respawn timer for fallen enemies; eliminated enemies stay out
+        if enemy.is_fallen && !contestant.is_eliminated() {
             if enemy.respawn_timer.tick(time.delta()).finished() {
-                // Respawn the enemy
+                // Respawn the enemy on the part of the platform it fell off
                 enemy.is_fallen = false;
-                enemy.state = EnemyState::Patrol;
-                transform.translation = RESPAWN_POSITION;
+                enemy.state = EnemyState::Recover;
+                transform.translation = arena.spawn_position(arena.home(transform.translation.xz()));
+                health.restore();
                 velocity.linvel = Vec3::ZERO;
                 velocity.angvel = Vec3::ZERO;
             }
@@ -1,124 +1,153 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
-use rand::Rng;
 
-use crate::components::{Enemy, EnemyState, Player, EnergyBoost};
+use crate::components::{
+    Enemy, EnemyState, EnergyBoost, GameSettings, HitStun, Player,
+};
+use crate::resources::{Arena, EnemyTuning, GameTuning};
 
-// Physics constants
-const BASE_MOVEMENT_FORCE: f32 = 25.0; // Reduced base force since it's now additive
-const MAX_SPEED: f32 = 12.0; // Keep the same max speed
-const FRICTION: f32 = 0.97; // Increased friction to better control the additive force
+/// Position and velocity of a duck, flattened onto the platform plane.
+struct DuckSnapshot {
+    entity: Entity,
+    position: Vec2,
+    velocity: Vec2,
+}
+
+/// Where to aim for a moving target: its position after the time it would
+/// take to get there at the current speed, up to `max_prediction` seconds.
+fn pursue(position: Vec2, speed: f32, target: &DuckSnapshot, max_prediction: f32) -> Vec2 {
+    let lead_time = (target.position.distance(position) / speed.max(1.0)).min(max_prediction);
+    target.position + target.velocity * lead_time
+}
+
+/// Desired velocity towards `goal` at `max_speed`, easing off inside
+/// `arrival_radius` when `arrive` is set.
+fn seek(position: Vec2, goal: Vec2, max_speed: f32, arrive: bool, tuning: &EnemyTuning) -> Vec2 {
+    let offset = goal - position;
+    let distance = offset.length();
+    let speed = if arrive && tuning.arrival_radius > 0.0 {
+        max_speed * (distance / tuning.arrival_radius).min(1.0)
+    } else {
+        max_speed
+    };
+    offset.normalize_or_zero() * speed
+}
+
+/// Push away from ducks inside `separation_radius`, stronger the closer they are.
+fn separation(position: Vec2, own: Entity, ignore: Option<Entity>, ducks: &[DuckSnapshot], tuning: &EnemyTuning) -> Vec2 {
+    ducks
+        .iter()
+        .filter(|duck| duck.entity != own && Some(duck.entity) != ignore)
+        .map(|duck| {
+            let away = position - duck.position;
+            let distance = away.length();
+            if distance >= tuning.separation_radius || distance <= f32::EPSILON {
+                Vec2::ZERO
+            } else {
+                away / distance * (1.0 - distance / tuning.separation_radius)
+            }
+        })
+        .sum::<Vec2>()
+        * tuning.separation_weight
+}
+
+/// Pull towards safer ground when the enemy's course would take it within
+/// `edge_margin` of the platform edge.
+fn edge_avoidance(position: Vec2, velocity: Vec2, arena: &Arena, tuning: &EnemyTuning) -> Vec2 {
+    let ahead = position + velocity * tuning.edge_lookahead;
+    let overshoot = tuning.edge_margin - arena.edge_distance(ahead);
+    if overshoot <= 0.0 {
+        return Vec2::ZERO;
+    }
+    arena.inward(ahead) * (overshoot / tuning.edge_margin.max(0.1)) * tuning.edge_weight
+}
 
+/// Steers every enemy towards the goal its utility AI last chose. Chase and
+/// ram goals lead the target duck, spot goals are approached with an
+/// arrival slowdown, and every enemy keeps clear of other ducks and the
+/// platform edge. Boosting enemies get the same extra force and speed limit
+/// as a boosting player.
 pub fn enemy_behavior(
-    mut enemy_query: Query<(Entity, (&mut Enemy, &Transform, &mut Velocity, &EnergyBoost))>,
-    player_query: Query<(Entity, &Transform, &Velocity), (With<Player>, Without<Enemy>)>, // Include player velocity
-    all_enemies_query: Query<(Entity, &Transform), With<Enemy>>, // Query for all enemies and their health, excluding EnergyBoost and current enemy
+    mut queries: ParamSet<(
+        Query<(Entity, &Transform, &Velocity), Or<(With<Player>, With<Enemy>)>>,
+        Query<(Entity, &mut Enemy, &Transform, &mut Velocity, &EnergyBoost, &HitStun)>,
+    )>,
+    arena: Res<Arena>,
     time: Res<Time>,
+    tuning: Res<GameTuning>,
+    settings: Res<GameSettings>,
 ) {
-    let mut rng = rand::thread_rng();
-    let player_entity = player_query.get_single().ok().map(|(entity, _, _)| entity);
-    let (player_pos, player_vel) = if let Ok((_, player_transform, player_velocity)) =
-        player_query.get_single()
-    {
-        (player_transform.translation, player_velocity.linvel)
-    } else {
-        return;
-    };
+    let windup_brake = tuning.boost.windup_brake;
+    let tuning = &tuning.enemy.for_difficulty(settings.difficulty);
+    // Ducks on the platform; fallen ones are parked out of the way below it
+    let ducks = queries
+        .p0()
+        .iter()
+        .filter(|(_, transform, _)| arena.is_standing(transform.translation))
+        .map(|(entity, transform, velocity)| DuckSnapshot {
+            entity,
+            position: transform.translation.xz(),
+            velocity: velocity.linvel.xz(),
+        })
+        .collect::<Vec<_>>();
 
-    for (enemy_entity, (mut enemy, transform, mut velocity, boost)) in enemy_query.iter_mut() {
-        if enemy.is_fallen {
+    for (entity, mut enemy, transform, mut velocity, boost, stun) in queries.p1().iter_mut() {
+        if enemy.is_fallen || stun.is_stunned() {
             continue;
         }
 
-        enemy.state_timer.tick(time.delta());
+        let position = transform.translation.xz();
+        let current = velocity.linvel.xz();
 
-        if enemy.state_timer.just_finished() {
-            enemy.state = match enemy.state {
-                EnemyState::Patrol => {
-                    if rng.gen_bool(0.95) { // Further increased chance to chase
-                        EnemyState::Chase
-                    } else {
-                        enemy.target_position = Some(Enemy::get_random_platform_position());
-                        EnemyState::Patrol
-                    }
+        let pursuing = matches!(enemy.state, EnemyState::Chase | EnemyState::Ram);
+        if pursuing {
+            let target = enemy.target.and_then(|target| ducks.iter().find(|duck| duck.entity == target));
+            match target {
+                Some(target) => {
+                    let aim = pursue(position, current.length(), target, tuning.max_prediction);
+                    enemy.target_position = Some(Vec3::new(aim.x, transform.translation.y, aim.y));
                 }
-                EnemyState::Chase => {
-                    if rng.gen_bool(0.15) { // Further reduced chance to stop chasing
-                        enemy.target = None; // Clear target when switching to patrol
-                        enemy.target_position = Some(Enemy::get_random_platform_position());
-                        EnemyState::Patrol
-                    } else {
-                        EnemyState::Chase
-                    }
+                None => {
+                    enemy.target = None; // Clear invalid target
+                    enemy.target_position = None;
                 }
-                EnemyState::Fallen => EnemyState::Patrol,
-            };
+            }
         }
 
-        // Simplified targeting logic
-        if enemy.state == EnemyState::Chase {
-            let mut target_pos = None;
+        // Brake while a ram is telegraphed, before pushing off
+        if boost.is_winding_up() {
+            velocity.linvel *= (-windup_brake * time.delta_seconds()).exp();
+            continue;
+        }
 
-            // If a target is already set, use it
-            if let Some(target_entity) = enemy.target {
-                if let Ok((_, target_transform)) = all_enemies_query.get(target_entity) {
-                    target_pos = Some(target_transform.translation);
-                } else if let Some(player_entity) = player_entity {
-                    if enemy.target.as_ref() == Some(&player_entity) { // Corrected comparison
-                        if let Ok((_, player_transform, player_velocity)) = player_query.get_single() {
-                            target_pos = Some(player_transform.translation);
-                        }
-                    }
-                } else {
-                    enemy.target = None; // Clear invalid target
-                }
-            } else {
-                // Try to target the player first
-                if let Some(player_entity) = &player_entity {
-                    enemy.target = Some(player_entity.clone()); // Correctly set target to player entity
-                    if let Ok((_, player_transform, player_velocity)) = player_query.get_single() {
-                        target_pos = Some(player_transform.translation);
-                    }
-                } else {
-                    // If no player, target any enemy
-                    for (entity, enemy_transform) in all_enemies_query.iter() { // Get entity and transform
-                        if entity != enemy_entity {
-                            enemy.target = Some(entity); // Correctly set target to enemy entity
-                            target_pos = Some(enemy_transform.translation); // Use enemy_transform here
-                            break;
-                        }
-                    }
-                }
-            }
+        let Some(goal) = enemy.target_position else {
+            continue;
+        };
+        let (max_speed, acceleration) = if boost.is_boosting {
+            (tuning.boost_max_speed, tuning.movement_force * tuning.boost_acceleration)
+        } else {
+            (tuning.max_speed, tuning.movement_force)
+        };
+        let arrive = matches!(enemy.state, EnemyState::Recover | EnemyState::Retreat);
+        // A ram commits to the direction it was aimed in when it started
+        let desired = if boost.is_boosting {
+            boost.direction.xz() * max_speed
+        } else {
+            seek(position, goal.xz(), max_speed, arrive, tuning)
+        };
+        let ignore = if pursuing { enemy.target } else { None };
 
+        let steering = (desired - current) / max_speed * tuning.steering_response
+            + separation(position, entity, ignore, &ducks, tuning)
+            + edge_avoidance(position, current, &arena, tuning);
+        let steering = steering.clamp_length_max(1.0) * acceleration;
 
-            if let Some(target_pos) = target_pos {
-                // Basic movement towards the target (no prediction or weakness check)
-                let base_direction = (target_pos - transform.translation).normalize();
-                let force = BASE_MOVEMENT_FORCE;
-                // Apply force additively without resetting velocity
-                velocity.linvel += base_direction * force * time.delta_seconds();
-                velocity.linvel *= FRICTION;
-                let speed = velocity.linvel.length();
-                if speed > MAX_SPEED {
-                    velocity.linvel = velocity.linvel.normalize() * MAX_SPEED;
-                }
-            }
-        } else if enemy.state == EnemyState::Patrol {
-            if enemy.target_position.is_none() {
-                enemy.target_position = Some(Enemy::get_random_platform_position());
-            }
-            if let Some(target_pos) = enemy.target_position {
-                let base_direction = (target_pos - transform.translation).normalize();
-                let force = BASE_MOVEMENT_FORCE;
-                // Apply force additively without resetting velocity
-                velocity.linvel += base_direction * force * time.delta_seconds();
-                velocity.linvel *= FRICTION;
-                let speed = velocity.linvel.length();
-                if speed > MAX_SPEED {
-                    velocity.linvel = velocity.linvel.normalize() * MAX_SPEED;
-                }
-            }
+        // Apply force additively without resetting velocity
+        velocity.linvel += Vec3::new(steering.x, 0.0, steering.y) * time.delta_seconds();
+        velocity.linvel *= (-tuning.friction_rate * time.delta_seconds()).exp();
+        let speed = velocity.linvel.length();
+        if speed > max_speed {
+            velocity.linvel = velocity.linvel.normalize() * max_speed;
         }
     }
 }
@@ -1,53 +1,53 @@
 use bevy::prelude::*;
-use bevy_rapier3d::prelude::{RigidBody, Velocity, Collider, LockedAxes, Damping, CollisionGroups, Group};
 use rand::Rng;
 
-use crate::components::{Enemy, EnemyState, EnergyBoost, PLATFORM_HEIGHT, DuckScore, DuckParams, spawn_duck};
+use crate::components::{
+    Enemy, EnemyState, EnergyBoost, Contestant, DuckParams, spawn_duck,
+    Personality, AiDecision, Health,
+};
+use crate::resources::{Difficulty, GameTuning, WaveDefinition};
 
-const SPAWN_POSITIONS: [(f32, f32); 6] = [
-    (-8.0, -8.0),
-    (-8.0, 8.0),
-    (8.0, -8.0),
-    (8.0, 8.0),
-    (0.0, -8.0),
-    (0.0, 8.0),
-];
+/// Spawns one enemy of `wave` at `position`, with its AI scaled for `difficulty`.
+pub fn spawn_enemy(
+    commands: &mut Commands,
+    rng: &mut impl Rng,
+    tuning: &GameTuning,
+    difficulty: Difficulty,
+    wave: &WaveDefinition,
+    name: String,
+    position: Vec3,
+) -> Entity {
+    // Some enemies are tougher than others
+    let health_range = &tuning.health;
+    let max_health = rng.gen_range(
+        health_range.enemy_min_health..=health_range.enemy_max_health.max(health_range.enemy_min_health),
+    ) * wave.health_multiplier;
+    let mut enemy = Enemy::new(&tuning.enemy.for_difficulty(difficulty));
+    enemy.state = EnemyState::Chase;
 
-pub fn spawn_enemies(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-) {
-    let mut rng = rand::thread_rng();
-    // Spawn initial enemies at corners
-    for (i, (x, z)) in SPAWN_POSITIONS.iter().enumerate() {
-        let health = rng.gen_range(75.0..150.0); // Increased health range for tougher enemies
-        let mut enemy = Enemy::new();
-        enemy.health = health;
-        enemy.state = EnemyState::Chase;
+    let enemy_entity = spawn_duck(
+        commands,
+        DuckParams {
+            body_radius: 0.5,
+            head_radius: 0.4,
+            bill_length: 0.4,
+            body_offset: Vec3::new(0.0, 0.0, 0.0),
+            head_offset: Vec3::new(0.0, 0.7, 0.0),
+            bill_offset: Vec3::new(0.2, 0.0, 0.0),
+            base_color: Color::rgb(0.8, 0.2, 0.2),
+            bill_color: Color::rgb(0.8, 0.6, 0.0),
+            position,
+            is_player: false,
+        },
+    );
 
-        let enemy_entity = spawn_duck(
-            &mut commands,
-            &mut meshes,
-            &mut materials,
-            DuckParams {
-                body_radius: 0.5,
-                head_radius: 0.4,
-                bill_length: 0.4,
-                body_offset: Vec3::new(0.0, 0.0, 0.0),
-                head_offset: Vec3::new(0.0, 0.7, 0.0),
-                bill_offset: Vec3::new(0.2, 0.0, 0.0),
-                base_color: Color::rgb(0.8, 0.2, 0.2),
-                bill_color: Color::rgb(0.8, 0.6, 0.0),
-                position: Vec3::new(*x, PLATFORM_HEIGHT + 2.0, *z),
-                is_player: false,
-            },
-        );
-
-        commands.entity(enemy_entity).insert((
-                    enemy,
-                    DuckScore::new(format!("Enemy {}", i + 1)),
-                    EnergyBoost::default(),
-                ));
-    }
+    commands.entity(enemy_entity).insert((
+        enemy,
+        Contestant::new(name, wave.lives.max(1)),
+        EnergyBoost::new(&tuning.boost),
+        Health::new(max_health, &tuning.health),
+        Personality::roll(rng),
+        AiDecision::default(),
+    ));
+    enemy_entity
 }
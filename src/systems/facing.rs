@@ -0,0 +1,64 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
+
+use crate::components::{Enemy, EnergyBoost, Player, PlayerInput};
+use crate::resources::GameTuning;
+
+/// Ducks slower than this keep their heading instead of turning toward
+/// their drift.
+const MIN_TURN_SPEED: f32 = 0.5;
+
+/// The way a duck faces on the XZ plane: along its bill, which points down
+/// +X in the model.
+pub fn facing(transform: &Transform) -> Vec3 {
+    let bill = transform.rotation * Vec3::X;
+    Vec3::new(bill.x, 0.0, bill.z).try_normalize().unwrap_or(Vec3::X)
+}
+
+/// Yaw that turns the bill toward `direction`.
+fn yaw_of(direction: Vec3) -> f32 {
+    (-direction.z).atan2(direction.x)
+}
+
+/// Turns every duck toward where it is going: a player's input, or its
+/// velocity when there is none, and an enemy's velocity. A boosting duck
+/// turns toward the direction locked at the start of its boost instead.
+/// Turning is done through angular velocity so rapier keeps the body in
+/// step, capped at the turn rate from the tuning file.
+pub fn face_movement(
+    mut query: Query<
+        (&Transform, &mut Velocity, &EnergyBoost, Option<&PlayerInput>, Has<Player>),
+        Or<(With<Player>, With<Enemy>)>,
+    >,
+    time: Res<Time>,
+    tuning: Res<GameTuning>,
+) {
+    let dt = time.delta_seconds();
+    if dt <= 0.0 {
+        return;
+    }
+
+    for (transform, mut velocity, boost, input, is_player) in query.iter_mut() {
+        let moving = Vec3::new(velocity.linvel.x, 0.0, velocity.linvel.z);
+        let steering = input
+            .map(|input| Vec3::new(input.movement.x, 0.0, -input.movement.y))
+            .filter(|direction| *direction != Vec3::ZERO);
+        let heading = if boost.is_boosting {
+            Some(boost.direction)
+        } else {
+            steering.or((moving.length() > MIN_TURN_SPEED).then_some(moving))
+        };
+
+        // Without a heading, stop any spin knocked into the duck by a hit
+        let Some(heading) = heading.and_then(Vec3::try_normalize) else {
+            velocity.angvel.y = 0.0;
+            continue;
+        };
+
+        let turn_rate = if is_player { tuning.player.turn_rate } else { tuning.enemy.turn_rate }.to_radians();
+        let turn = (yaw_of(heading) - yaw_of(facing(transform)) + PI).rem_euclid(TAU) - PI;
+        velocity.angvel.y = (turn / dt).clamp(-turn_rate, turn_rate);
+    }
+}
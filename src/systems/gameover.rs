@@ -1,23 +1,39 @@
 use bevy::prelude::*;
 
 use crate::{
-    components::{GameOverScreen, FinalScoreText, GameOverButton, GameOverButtonAction, BearScore, BoostIndicator},
+    components::{GameOverScreen, FinalScoreText, GameOverButton, GameOverButtonAction, Contestant, BoostIndicator},
+    resources::MatchResult,
     styles::*,
     states::GameState,
+    systems::new_record_banner,
 };
 
-pub fn spawn_game_over_screen(mut commands: Commands, asset_server: Res<AssetServer>, score_query: Query<&BearScore>) {
-    // Find the player's score
-    let mut player_score = 0;
-    let mut player_name = "Player".to_string();
-    
-    for score in score_query.iter() {
-        if score.name == "Player" {
-            player_score = score.value;
-            player_name = score.name.clone();
-            break;
-        }
-    }
+pub fn spawn_game_over_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    result: Res<MatchResult>,
+) {
+    // Every local player's standing from the match that just ended
+    let players = result
+        .record
+        .iter()
+        .flat_map(|record| record.standings.iter())
+        .filter(|entry| entry.human)
+        .collect::<Vec<_>>();
+    let summary = if players.is_empty() {
+        "Final Score: 0".to_string()
+    } else {
+        players
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{}'s Final Score: {}\nFalls: {}  Knockouts: {}  Eliminations: {}  Best Streak: {}",
+                    entry.name, entry.points, entry.falls, entry.knockouts, entry.eliminations, entry.best_streak
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
 
     let game_over_screen = commands
         .spawn((
@@ -56,6 +72,10 @@ pub fn spawn_game_over_screen(mut commands: Commands, asset_server: Res<AssetSer
         });
     }).set_parent(game_over_screen);
 
+    if result.new_record {
+        commands.spawn(new_record_banner(&asset_server)).set_parent(game_over_screen);
+    }
+
     // Final Score
     commands.spawn((
         NodeBundle {
@@ -64,7 +84,7 @@ pub fn spawn_game_over_screen(mut commands: Commands, asset_server: Res<AssetSer
                 justify_content: JustifyContent::Center,
                 align_items: AlignItems::Center,
                 width: Val::Px(400.0),
-                height: Val::Px(80.0),
+                min_height: Val::Px(80.0),
                 ..default()
             },
             ..default()
@@ -73,7 +93,7 @@ pub fn spawn_game_over_screen(mut commands: Commands, asset_server: Res<AssetSer
     )).with_children(|parent| {
         parent.spawn(TextBundle {
             text: Text::from_section(
-                format!("{}'s Final Score: {}", player_name, player_score),
+                summary,
                 TextStyle {
                     font_size: 32.0,
                     color: Color::WHITE,
@@ -122,7 +142,7 @@ pub fn handle_game_over_input(
     >,
     mut next_state: ResMut<NextState<GameState>>,
     mut commands: Commands,
-    score_query: Query<Entity, With<BearScore>>,
+    contestant_query: Query<Entity, With<Contestant>>,
     boost_indicator_query: Query<Entity, With<BoostIndicator>>,
 ) {
     for (interaction, mut color, button) in button_query.iter_mut() {
@@ -134,8 +154,8 @@ pub fn handle_game_over_input(
                         next_state.set(GameState::InGame);
                     },
                     GameOverButtonAction::MainMenu => {
-                        // Clean up score entities
-                        for entity in score_query.iter() {
+                        // Clean up contestant entities
+                        for entity in contestant_query.iter() {
                             commands.entity(entity).despawn_recursive();
                         }
                         
@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+use crate::components::Health;
+use crate::resources::GameTuning;
+
+/// Regains health for ducks that haven't been hit for a while.
+pub fn recover_health(mut health_query: Query<&mut Health>, time: Res<Time>, tuning: Res<GameTuning>) {
+    for mut health in health_query.iter_mut() {
+        if health.recovery_timer.tick(time.delta()).finished() {
+            health.current = (health.current + tuning.health.recovery_rate * time.delta_seconds()).min(health.max);
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use std::cmp::Ordering;
+
+use bevy::prelude::*;
+
+use crate::{
+    components::{
+        HighScoreColumn, HighScoreMenu, HighScoreSortButton, HighScoreTable, MenuButtonAction,
+    },
+    resources::{ActionState, MatchHistory, MatchRecord, PlayerAction},
+    states::GameState,
+    styles::*,
+    systems::spawn_menu_button,
+};
+
+/// Most matches the table shows at once.
+const MAX_TABLE_ROWS: usize = 12;
+
+const TABLE_ROW_HEIGHT: f32 = 28.0;
+
+/// Table columns with their header label and width in pixels.
+const COLUMNS: [(HighScoreColumn, &str, f32); 6] = [
+    (HighScoreColumn::Date, "Date", 190.0),
+    (HighScoreColumn::Mode, "Mode", 190.0),
+    (HighScoreColumn::Seed, "Seed", 230.0),
+    (HighScoreColumn::Score, "Score", 90.0),
+    (HighScoreColumn::Placement, "Place", 90.0),
+    (HighScoreColumn::Duration, "Time", 90.0),
+];
+
+/// How the high-score table is currently sorted.
+#[derive(Resource)]
+pub struct HighScoreSort {
+    pub column: HighScoreColumn,
+    pub descending: bool,
+}
+
+impl Default for HighScoreSort {
+    fn default() -> Self {
+        Self {
+            column: HighScoreColumn::Score,
+            descending: true,
+        }
+    }
+}
+
+/// Header label of a column, showing the sort direction when it is sorted on.
+#[derive(Component)]
+pub struct HighScoreHeaderText(pub HighScoreColumn);
+
+/// Large banner for the game-over and win screens when a match beat every earlier score.
+pub fn new_record_banner(asset_server: &AssetServer) -> TextBundle {
+    TextBundle::from_section(
+        "New Record!",
+        TextStyle {
+            font_size: 48.0,
+            color: Color::rgb(1.0, 0.6, 0.1),
+            ..get_button_text_style(asset_server)
+        },
+    )
+}
+
+fn get_table_text_style(asset_server: &AssetServer) -> TextStyle {
+    TextStyle {
+        font_size: 20.0,
+        ..get_button_text_style(asset_server)
+    }
+}
+
+pub fn spawn_high_score_screen(mut commands: Commands, asset_server: Res<AssetServer>) {
+    // Camera
+    commands.spawn(Camera2dBundle::default());
+
+    let high_score_menu = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                },
+                background_color: Color::NONE.into(), // Transparent background to show shader
+                ..default()
+            },
+            HighScoreMenu,
+        ))
+        .id();
+
+    // Title
+    commands.spawn(NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Row,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            width: Val::Px(400.0),
+            height: Val::Px(120.0),
+            ..default()
+        },
+        ..default()
+    }).with_children(|parent| {
+        parent.spawn(TextBundle {
+            text: Text::from_section("High Scores", get_title_text_style(&asset_server)),
+            ..default()
+        });
+    }).set_parent(high_score_menu);
+
+    // Header; each column header sorts the table
+    commands.spawn(NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(4.0),
+            ..default()
+        },
+        ..default()
+    }).with_children(|parent| {
+        for (column, _, width) in COLUMNS {
+            parent.spawn((
+                ButtonBundle {
+                    style: Style {
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        width: Val::Px(width),
+                        height: Val::Px(36.0),
+                        ..default()
+                    },
+                    background_color: NORMAL_BUTTON_COLOR.into(),
+                    ..default()
+                },
+                HighScoreSortButton(column),
+            )).with_children(|parent| {
+                parent.spawn((
+                    TextBundle::from_section("", get_table_text_style(&asset_server)),
+                    HighScoreHeaderText(column),
+                ));
+            });
+        }
+    }).set_parent(high_score_menu);
+
+    // Rows are filled in by `refresh_high_score_table`
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                min_height: Val::Px(TABLE_ROW_HEIGHT * MAX_TABLE_ROWS as f32),
+                ..default()
+            },
+            ..default()
+        },
+        HighScoreTable,
+    )).set_parent(high_score_menu);
+
+    // Buttons
+    spawn_menu_button(&mut commands, &asset_server, "Back", MenuButtonAction::Back, high_score_menu);
+}
+
+/// Sorts by a column when its header is pressed; pressing it again flips the order.
+pub fn handle_high_score_input(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor, &HighScoreSortButton),
+        (Changed<Interaction>, With<Button>),
+    >,
+    actions: Res<ActionState>,
+    mut sort: ResMut<HighScoreSort>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, mut color, button) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON_COLOR.into();
+                if sort.column == button.0 {
+                    sort.descending = !sort.descending;
+                } else {
+                    sort.column = button.0;
+                    // Best first: high scores, recent dates, low placements
+                    sort.descending = button.0 != HighScoreColumn::Placement;
+                }
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {}
+        }
+    }
+
+    if actions.just_pressed(PlayerAction::Back) {
+        next_state.set(GameState::MainMenu);
+    }
+}
+
+fn compare_records(a: &MatchRecord, b: &MatchRecord, column: HighScoreColumn) -> Ordering {
+    match column {
+        HighScoreColumn::Date => a.timestamp.cmp(&b.timestamp),
+        HighScoreColumn::Mode => a.mode_label().cmp(&b.mode_label()),
+        HighScoreColumn::Seed => a.seed.cmp(&b.seed),
+        HighScoreColumn::Score => a.best_score().cmp(&b.best_score()),
+        HighScoreColumn::Placement => a.placement.cmp(&b.placement),
+        HighScoreColumn::Duration => a.duration_secs.total_cmp(&b.duration_secs),
+    }
+}
+
+fn cell_text(record: &MatchRecord, column: HighScoreColumn) -> String {
+    match column {
+        HighScoreColumn::Date => record.date_label(),
+        HighScoreColumn::Mode => record.mode_label(),
+        HighScoreColumn::Seed => record.seed.to_string(),
+        HighScoreColumn::Score => record
+            .best_score()
+            .map_or_else(|| "-".to_string(), |score| score.to_string()),
+        HighScoreColumn::Placement => format!("{}/{}", record.placement, record.standings.len()),
+        HighScoreColumn::Duration => record.duration_label(),
+    }
+}
+
+/// Rebuilds the table rows when the screen opens or the sort changes.
+pub fn refresh_high_score_table(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    history: Res<MatchHistory>,
+    sort: Res<HighScoreSort>,
+    table_query: Query<(Entity, Ref<HighScoreTable>)>,
+    mut header_query: Query<(&mut Text, &HighScoreHeaderText)>,
+) {
+    for (table, marker) in table_query.iter() {
+        if !marker.is_added() && !sort.is_changed() && !history.is_changed() {
+            continue;
+        }
+
+        let mut records = history.records.iter().collect::<Vec<_>>();
+        records.sort_by(|a, b| {
+            let ordering = compare_records(a, b, sort.column);
+            if sort.descending { ordering.reverse() } else { ordering }
+        });
+
+        commands.entity(table).despawn_descendants();
+        commands.entity(table).with_children(|parent| {
+            if records.is_empty() {
+                parent.spawn(TextBundle::from_section(
+                    "No matches played yet",
+                    get_table_text_style(&asset_server),
+                ));
+            }
+
+            for record in records.into_iter().take(MAX_TABLE_ROWS) {
+                parent.spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Px(4.0),
+                        height: Val::Px(TABLE_ROW_HEIGHT),
+                        ..default()
+                    },
+                    ..default()
+                }).with_children(|parent| {
+                    for (column, _, width) in COLUMNS {
+                        parent.spawn(TextBundle {
+                            text: Text::from_section(
+                                cell_text(record, column),
+                                get_table_text_style(&asset_server),
+                            ),
+                            style: Style {
+                                width: Val::Px(width),
+                                ..default()
+                            },
+                            ..default()
+                        });
+                    }
+                });
+            }
+        });
+
+        for (mut text, header) in header_query.iter_mut() {
+            let (_, label, _) = COLUMNS
+                .iter()
+                .find(|(column, _, _)| *column == header.0)
+                .copied()
+                .unwrap_or((header.0, "", 0.0));
+            text.sections[0].value = match (sort.column == header.0, sort.descending) {
+                (true, true) => format!("{} v", label),
+                (true, false) => format!("{} ^", label),
+                (false, _) => label.to_string(),
+            };
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use bevy::{prelude::*, utils::HashSet};
+
+use crate::components::{GameSettings, Player, PlayerInput};
+use crate::resources::{ActionState, InputBindings, InputSource, PlayerAction};
+
+/// Rebuilds the menu `ActionState` and every player's own `ActionState` from
+/// the keyboard, connected gamepads and the bindings in `GameSettings`.
+pub fn update_action_state(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    settings: Res<GameSettings>,
+    mut actions: ResMut<ActionState>,
+    mut player_query: Query<(&InputSource, &mut ActionState), With<Player>>,
+) {
+    // Stable order so `InputSource::Gamepad(n)` keeps pointing at the same pad
+    let mut connected = gamepads.iter().collect::<Vec<_>>();
+    connected.sort_by_key(|gamepad| gamepad.id);
+
+    let devices = Devices {
+        bindings: &settings.bindings,
+        keyboard_input: &keyboard_input,
+        gamepads: &connected,
+        gamepad_buttons: &gamepad_buttons,
+        gamepad_axes: &gamepad_axes,
+    };
+
+    let (pressed, stick) = devices.read(InputSource::Any);
+    actions.update(pressed, stick);
+
+    for (source, mut player_actions) in player_query.iter_mut() {
+        let (pressed, stick) = devices.read(*source);
+        player_actions.update(pressed, stick);
+    }
+}
+
+/// Everything needed to read one `InputSource` this frame.
+struct Devices<'a> {
+    bindings: &'a InputBindings,
+    keyboard_input: &'a Input<KeyCode>,
+    gamepads: &'a [Gamepad],
+    gamepad_buttons: &'a Input<GamepadButton>,
+    gamepad_axes: &'a Axis<GamepadAxis>,
+}
+
+impl Devices<'_> {
+    /// Held actions and the dead-zoned left stick for `source`.
+    fn read(&self, source: InputSource) -> (HashSet<PlayerAction>, Vec2) {
+        let (primary_keys, secondary_keys, gamepads) = match source {
+            InputSource::Any => (true, true, self.gamepads),
+            InputSource::PrimaryKeys => (true, false, &[][..]),
+            InputSource::SecondaryKeys => (false, true, &[][..]),
+            InputSource::Gamepad(index) => (
+                false,
+                false,
+                self.gamepads.get(index..=index).unwrap_or(&[]),
+            ),
+        };
+
+        let bindings = self.bindings;
+        let pressed = PlayerAction::ALL
+            .into_iter()
+            .filter(|action| {
+                (primary_keys
+                    && self.keyboard_input.any_pressed(bindings.keys_for(*action).iter().copied()))
+                    || (secondary_keys
+                        && self
+                            .keyboard_input
+                            .any_pressed(bindings.secondary_keys_for(*action).iter().copied()))
+                    || gamepads.iter().any(|gamepad| {
+                        self.gamepad_buttons.any_pressed(
+                            bindings
+                                .gamepad_buttons_for(*action)
+                                .iter()
+                                .map(|button_type| GamepadButton::new(*gamepad, *button_type)),
+                        )
+                    })
+            })
+            .collect::<HashSet<_>>();
+
+        // Use whichever stick is deflected the most
+        let stick = gamepads
+            .iter()
+            .map(|gamepad| {
+                let x = self
+                    .gamepad_axes
+                    .get(GamepadAxis::new(*gamepad, GamepadAxisType::LeftStickX))
+                    .unwrap_or(0.0);
+                let y = self
+                    .gamepad_axes
+                    .get(GamepadAxis::new(*gamepad, GamepadAxisType::LeftStickY))
+                    .unwrap_or(0.0);
+                bindings.apply_dead_zone(Vec2::new(x, y))
+            })
+            .max_by(|a, b| a.length_squared().total_cmp(&b.length_squared()))
+            .unwrap_or(Vec2::ZERO);
+
+        (pressed, stick)
+    }
+}
+
+/// Copies each player's movement and boost actions into their `PlayerInput` once per frame.
+pub fn read_player_input(mut query: Query<(&ActionState, &mut PlayerInput), With<Player>>) {
+    for (actions, mut input) in query.iter_mut() {
+        input.movement = actions.move_axis();
+        input.boost_held = actions.pressed(PlayerAction::Boost);
+        input.boost_pressed |= actions.just_pressed(PlayerAction::Boost);
+        input.boost_released |= actions.just_released(PlayerAction::Boost);
+    }
+}
+
+/// Clears the latched press/release flags once a gameplay tick has used them.
+pub fn clear_player_input_edges(mut query: Query<&mut PlayerInput>) {
+    for mut input in query.iter_mut() {
+        input.boost_pressed = false;
+        input.boost_released = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::input::{
+        gamepad::{
+            GamepadAxisChangedEvent, GamepadButtonChangedEvent, GamepadConnection, GamepadConnectionEvent,
+            GamepadEvent, GamepadInfo,
+        },
+        InputPlugin, InputSystem,
+    };
+
+    use super::*;
+    use crate::components::{MenuButton, MenuButtonAction};
+    use crate::systems::{navigate_menu_focus, MenuFocus};
+
+    const PAD: Gamepad = Gamepad { id: 0 };
+
+    /// Just the input plugin, the action systems and two menu buttons, top one first.
+    fn input_app() -> (App, [Entity; 2]) {
+        let mut app = App::new();
+        app.add_plugins(InputPlugin)
+            .init_resource::<GameSettings>()
+            .init_resource::<ActionState>()
+            .init_resource::<MenuFocus>()
+            .add_systems(PreUpdate, update_action_state.after(InputSystem))
+            .add_systems(Update, navigate_menu_focus);
+
+        let buttons = [(MenuButtonAction::Play, 100.0), (MenuButtonAction::Players, 200.0)].map(|(action, y)| {
+            app.world
+                .spawn((
+                    MenuButton { action },
+                    GlobalTransform::from_xyz(0.0, y, 0.0),
+                    Interaction::None,
+                ))
+                .id()
+        });
+        app.world.spawn((Player::new(0), InputSource::Gamepad(0), ActionState::default()));
+        (app, buttons)
+    }
+
+    fn send(app: &mut App, event: impl Into<GamepadEvent>) {
+        app.world.send_event(event.into());
+        app.update();
+    }
+
+    fn player_actions(app: &mut App) -> &ActionState {
+        app.world
+            .query_filtered::<&ActionState, With<Player>>()
+            .single(&app.world)
+    }
+
+    #[test]
+    fn gamepad_events_drive_actions_and_menu_focus() {
+        let (mut app, [top, bottom]) = input_app();
+
+        let connected = GamepadConnection::Connected(GamepadInfo { name: "Test pad".to_string() });
+        send(&mut app, GamepadConnectionEvent::new(PAD, connected));
+        assert!(app.world.resource::<Gamepads>().contains(PAD));
+        assert_eq!(app.world.resource::<MenuFocus>().focused, None);
+
+        // The D-pad grabs focus on the top button first
+        send(&mut app, GamepadButtonChangedEvent::new(PAD, GamepadButtonType::DPadDown, 1.0));
+        assert!(app.world.resource::<ActionState>().just_pressed(PlayerAction::MoveBack));
+        assert!(player_actions(&mut app).pressed(PlayerAction::MoveBack));
+        assert_eq!(app.world.resource::<MenuFocus>().focused, Some(top));
+
+        send(&mut app, GamepadButtonChangedEvent::new(PAD, GamepadButtonType::DPadDown, 0.0));
+        assert!(app.world.resource::<ActionState>().just_released(PlayerAction::MoveBack));
+        assert_eq!(app.world.resource::<MenuFocus>().focused, Some(top));
+
+        // Pushing the stick down past the press threshold moves focus like the D-pad
+        send(&mut app, GamepadAxisChangedEvent::new(PAD, GamepadAxisType::LeftStickY, -0.9));
+        let actions = app.world.resource::<ActionState>();
+        assert!(actions.just_pressed(PlayerAction::MoveBack));
+        assert!(actions.move_axis().abs_diff_eq(Vec2::new(0.0, -0.875), 1e-5));
+        assert!(player_actions(&mut app).move_axis().abs_diff_eq(Vec2::new(0.0, -0.875), 1e-5));
+        assert_eq!(app.world.resource::<MenuFocus>().focused, Some(bottom));
+
+        // A stick inside the dead zone reads as centred
+        send(&mut app, GamepadAxisChangedEvent::new(PAD, GamepadAxisType::LeftStickY, -0.1));
+        assert_eq!(app.world.resource::<ActionState>().move_axis(), Vec2::ZERO);
+
+        send(&mut app, GamepadButtonChangedEvent::new(PAD, GamepadButtonType::South, 1.0));
+        assert!(app.world.resource::<ActionState>().just_pressed(PlayerAction::Confirm));
+        assert_eq!(app.world.resource::<MenuFocus>().pressed, Some(bottom));
+        assert_eq!(*app.world.get::<Interaction>(bottom).unwrap(), Interaction::Pressed);
+        assert_eq!(*app.world.get::<Interaction>(top).unwrap(), Interaction::None);
+    }
+
+    #[test]
+    fn unplugged_gamepad_releases_its_actions() {
+        let (mut app, _) = input_app();
+
+        let connected = GamepadConnection::Connected(GamepadInfo { name: "Test pad".to_string() });
+        send(&mut app, GamepadConnectionEvent::new(PAD, connected));
+        send(&mut app, GamepadButtonChangedEvent::new(PAD, GamepadButtonType::RightTrigger2, 1.0));
+        assert!(player_actions(&mut app).pressed(PlayerAction::Boost));
+
+        send(&mut app, GamepadConnectionEvent::new(PAD, GamepadConnection::Disconnected));
+        assert!(!app.world.resource::<Gamepads>().contains(PAD));
+        assert!(player_actions(&mut app).just_released(PlayerAction::Boost));
+        assert!(!app.world.resource::<ActionState>().pressed(PlayerAction::Boost));
+    }
+}
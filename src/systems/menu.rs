@@ -1,12 +1,20 @@
 use bevy::{app::AppExit, prelude::*};
 
 use crate::{
-    components::{MainMenu, MenuButton, MenuButtonAction, SettingsMenu},
+    components::{
+        GameSettings, HighScoreMenu, MainMenu, MenuButton, MenuButtonAction, SettingsMenu,
+        MAX_LOCAL_PLAYERS,
+    },
+    resources::{ArenaChoice, Difficulty},
     styles::*,
     states::GameState,
 };
 
-pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn spawn_main_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+) {
     // Camera
     commands.spawn(Camera2dBundle::default());
 
@@ -49,59 +57,69 @@ pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
 
     // Buttons
     spawn_menu_button(&mut commands, &asset_server, "Play", MenuButtonAction::Play, main_menu);
+    spawn_menu_button(
+        &mut commands,
+        &asset_server,
+        &players_label(settings.player_count),
+        MenuButtonAction::Players,
+        main_menu,
+    );
+    spawn_menu_button(
+        &mut commands,
+        &asset_server,
+        &difficulty_label(settings.difficulty),
+        MenuButtonAction::Difficulty,
+        main_menu,
+    );
+    spawn_menu_button(
+        &mut commands,
+        &asset_server,
+        &arena_label(settings.arena),
+        MenuButtonAction::Arena,
+        main_menu,
+    );
+    spawn_menu_button(
+        &mut commands,
+        &asset_server,
+        &sudden_death_label(settings.sudden_death),
+        MenuButtonAction::SuddenDeath,
+        main_menu,
+    );
+    spawn_menu_button(
+        &mut commands,
+        &asset_server,
+        &tilting_label(settings.tilting),
+        MenuButtonAction::Tilting,
+        main_menu,
+    );
+    spawn_menu_button(&mut commands, &asset_server, "High Scores", MenuButtonAction::HighScores, main_menu);
     spawn_menu_button(&mut commands, &asset_server, "Settings", MenuButtonAction::Settings, main_menu);
     spawn_menu_button(&mut commands, &asset_server, "Quit", MenuButtonAction::Quit, main_menu);
     spawn_menu_button(&mut commands, &asset_server, "Secret", MenuButtonAction::Secret, main_menu);
     spawn_menu_button(&mut commands, &asset_server, "Test Win", MenuButtonAction::TestWin, main_menu);
 }
 
-pub fn spawn_settings_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
-    // Camera
-    commands.spawn(Camera2dBundle::default());
+fn players_label(player_count: usize) -> String {
+    format!("Players: {}", player_count.clamp(1, MAX_LOCAL_PLAYERS))
+}
 
-    let settings_menu = commands
-        .spawn((
-            NodeBundle {
-                style: Style {
-                    width: Val::Percent(100.0),
-                    height: Val::Percent(100.0),
-                    flex_direction: FlexDirection::Column,
-                    justify_content: JustifyContent::Center,
-                    align_items: AlignItems::Center,
-                    row_gap: Val::Px(20.0),
-                    ..default()
-                },
-                background_color: Color::NONE.into(), // Transparent background to show shader
-                ..default()
-            },
-            SettingsMenu,
-        ))
-        .id();
+fn difficulty_label(difficulty: Difficulty) -> String {
+    format!("AI: {}", difficulty.label())
+}
 
-    // Title
-    commands.spawn(NodeBundle {
-        style: Style {
-            flex_direction: FlexDirection::Row,
-            justify_content: JustifyContent::Center,
-            align_items: AlignItems::Center,
-            width: Val::Px(300.0),
-            height: Val::Px(120.0),
-            ..default()
-        },
-        ..default()
-    }).with_children(|parent| {
-        parent.spawn(TextBundle {
-            text: Text::from_section("Settings", get_title_text_style(&asset_server)),
-            ..default()
-        });
-    }).set_parent(settings_menu);
+fn arena_label(arena: ArenaChoice) -> String {
+    format!("Arena: {}", arena.label())
+}
 
-    // Buttons
-    spawn_menu_button(&mut commands, &asset_server, "Back", MenuButtonAction::Back, settings_menu);
+fn sudden_death_label(sudden_death: bool) -> String {
+    format!("Sudden Death: {}", if sudden_death { "On" } else { "Off" })
 }
 
+fn tilting_label(tilting: bool) -> String {
+    format!("Platform: {}", if tilting { "Tilting" } else { "Fixed" })
+}
 
-fn spawn_menu_button(
+pub fn spawn_menu_button(
     commands: &mut Commands,
     asset_server: &AssetServer,
     text: &str,
@@ -127,21 +145,53 @@ fn spawn_menu_button(
     .set_parent(parent);
 }
 
+/// Replaces the text of a menu button, which sits on its child.
+fn set_button_label(children: &Children, text_query: &mut Query<&mut Text>, label: String) {
+    for child in children.iter() {
+        if let Ok(mut text) = text_query.get_mut(*child) {
+            text.sections[0].value = label.clone();
+        }
+    }
+}
+
 pub fn handle_menu_buttons(
     mut button_query: Query<
-        (&Interaction, &mut BackgroundColor, &MenuButton),
+        (&Interaction, &mut BackgroundColor, &MenuButton, &Children),
         (Changed<Interaction>, With<Button>),
     >,
+    mut text_query: Query<&mut Text>,
+    mut settings: ResMut<GameSettings>,
     mut app_exit_events: EventWriter<AppExit>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
-    for (interaction, mut color, menu_button) in button_query.iter_mut() {
+    for (interaction, mut color, menu_button, children) in button_query.iter_mut() {
         match *interaction {
             Interaction::Pressed => {
                 *color = PRESSED_BUTTON_COLOR.into();
                 match menu_button.action {
                     MenuButtonAction::Quit => app_exit_events.send(AppExit),
                     MenuButtonAction::Play => next_state.set(GameState::InGame),
+                    MenuButtonAction::Players => {
+                        settings.player_count = settings.player_count % MAX_LOCAL_PLAYERS + 1;
+                        set_button_label(children, &mut text_query, players_label(settings.player_count));
+                    }
+                    MenuButtonAction::Difficulty => {
+                        settings.difficulty = settings.difficulty.next();
+                        set_button_label(children, &mut text_query, difficulty_label(settings.difficulty));
+                    }
+                    MenuButtonAction::Arena => {
+                        settings.arena = settings.arena.next();
+                        set_button_label(children, &mut text_query, arena_label(settings.arena));
+                    }
+                    MenuButtonAction::SuddenDeath => {
+                        settings.sudden_death = !settings.sudden_death;
+                        set_button_label(children, &mut text_query, sudden_death_label(settings.sudden_death));
+                    }
+                    MenuButtonAction::Tilting => {
+                        settings.tilting = !settings.tilting;
+                        set_button_label(children, &mut text_query, tilting_label(settings.tilting));
+                    }
+                    MenuButtonAction::HighScores => next_state.set(GameState::HighScores),
                     MenuButtonAction::Settings => next_state.set(GameState::Settings),
                     MenuButtonAction::Back => next_state.set(GameState::MainMenu),
                     MenuButtonAction::Secret => next_state.set(GameState::SecretScene),
@@ -160,7 +210,7 @@ pub fn handle_menu_buttons(
 
 pub fn cleanup_menu(
     mut commands: Commands,
-    menu_query: Query<Entity, Or<(With<MainMenu>, With<SettingsMenu>)>>,
+    menu_query: Query<Entity, Or<(With<MainMenu>, With<SettingsMenu>, With<HighScoreMenu>)>>,
     camera_query: Query<Entity, With<Camera>>,
 ) {
     for entity in menu_query.iter() {
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+
+use crate::components::{GameOverButton, HighScoreSortButton, MenuButton, PauseButton, SettingsControl};
+use crate::systems::RestartButton;
+use crate::resources::{ActionState, PlayerAction};
+use crate::styles::{HOVERED_BUTTON_COLOR, NORMAL_BUTTON_COLOR};
+
+type NavigableButton = Or<(
+    With<MenuButton>,
+    With<PauseButton>,
+    With<GameOverButton>,
+    With<RestartButton>,
+    With<SettingsControl>,
+    With<HighScoreSortButton>,
+)>;
+
+/// Which menu button has gamepad/keyboard focus, and which one focus
+/// navigation pressed this frame.
+#[derive(Resource, Default)]
+pub struct MenuFocus {
+    pub focused: Option<Entity>,
+    pub pressed: Option<Entity>,
+}
+
+/// Moves focus between the buttons on screen with the move actions (D-pad,
+/// stick, arrows) and presses the focused one on Confirm. Pressing sets
+/// `Interaction::Pressed`, so each menu's own button handler runs the action.
+pub fn navigate_menu_focus(
+    actions: Res<ActionState>,
+    mut focus: ResMut<MenuFocus>,
+    mut button_query: Query<(Entity, &GlobalTransform, &mut Interaction), NavigableButton>,
+) {
+    // Buttons in on-screen order, top to bottom
+    let mut buttons = button_query
+        .iter()
+        .map(|(entity, transform, _)| (entity, transform.translation()))
+        .collect::<Vec<_>>();
+    buttons.sort_by(|a, b| a.1.y.total_cmp(&b.1.y).then(a.1.x.total_cmp(&b.1.x)));
+
+    let current = focus
+        .focused
+        .and_then(|focused| buttons.iter().position(|(entity, _)| *entity == focused));
+    if current.is_none() {
+        focus.focused = None;
+    }
+    if buttons.is_empty() {
+        return;
+    }
+
+    let next = actions.just_pressed(PlayerAction::MoveBack);
+    let previous = actions.just_pressed(PlayerAction::MoveForward);
+    let confirm = actions.just_pressed(PlayerAction::Confirm);
+
+    let index = match current {
+        None if next || previous || confirm => 0,
+        None => return,
+        Some(index) if next => (index + 1) % buttons.len(),
+        Some(index) if previous => (index + buttons.len() - 1) % buttons.len(),
+        Some(index) => index,
+    };
+    let focused = buttons[index].0;
+    focus.focused = Some(focused);
+
+    // The first Confirm only grabs focus, so the default button isn't activated blindly
+    if confirm && current.is_some() {
+        if let Ok((_, _, mut interaction)) = button_query.get_mut(focused) {
+            *interaction = Interaction::Pressed;
+            focus.pressed = Some(focused);
+        }
+    }
+}
+
+/// Releases the button focus navigation pressed and tints the focused button.
+pub fn highlight_menu_focus(
+    mut focus: ResMut<MenuFocus>,
+    mut button_query: Query<(Entity, &mut Interaction, &mut BackgroundColor), NavigableButton>,
+) {
+    if let Some(pressed) = focus.pressed.take() {
+        if let Ok((_, mut interaction, _)) = button_query.get_mut(pressed) {
+            interaction.set_if_neq(Interaction::None);
+        }
+    }
+
+    for (entity, interaction, mut color) in button_query.iter_mut() {
+        if *interaction == Interaction::None {
+            *color = if focus.focused == Some(entity) {
+                HOVERED_BUTTON_COLOR.into()
+            } else {
+                NORMAL_BUTTON_COLOR.into()
+            };
+        }
+    }
+}
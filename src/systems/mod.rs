@@ -1,17 +1,28 @@
 mod menu;
 mod player;
 mod boost;
-pub mod score;
 mod ui;
 mod gameover;
 mod pause;
 mod powerup;
 mod win;
 mod camera;
+mod platform;
+mod visuals;
+mod input;
+mod menu_focus;
+mod settings_menu;
+mod high_scores;
+mod health;
+mod facing;
+mod combat;
+mod waves;
+mod sudden_death;
 
 mod enemy_spawning;
 mod enemy_falling;
 mod enemy_movement;
+mod enemy_ai;
 
 pub use menu::*;
 pub use player::*;
@@ -22,18 +33,28 @@ pub use pause::*;
 pub use powerup::*;
 pub use win::*;
 pub use camera::*;
+pub use platform::*;
+pub use visuals::*;
+pub use input::*;
+pub use menu_focus::*;
+pub use settings_menu::*;
+pub use high_scores::*;
+pub use health::*;
+pub use facing::*;
+pub use combat::*;
+pub use waves::*;
+pub use sudden_death::*;
 
-pub use enemy_spawning::spawn_enemies;
+pub use enemy_spawning::spawn_enemy;
 pub use enemy_falling::handle_enemy_falls;
 pub use enemy_movement::enemy_behavior;
+pub use enemy_ai::*;
 
 use bevy::prelude::*;
 
 // Create a system set for common game mechanics
-/// System set for organizing and configuring game mechanics systems
-/// Even though variants appear unused, they are used for system configuration
+/// System set for ordering the fixed-timestep gameplay systems around the physics step
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
-#[allow(dead_code)]
 pub enum GameMechanicsSet {
     Movement,
     EnemyBehavior,
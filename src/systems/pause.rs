@@ -3,6 +3,7 @@ use bevy::app::AppExit;
 
 use crate::{
     components::{PauseMenu, PauseButton, PauseButtonAction, PauseState},
+    resources::{ActionState, PlayerAction},
     styles::*,
     states::GameState,
 };
@@ -10,12 +11,12 @@ use crate::{
 // Removed debug_print function
 
 pub fn toggle_pause(
-    keyboard_input: Res<Input<KeyCode>>,
+    actions: Res<ActionState>,
     current_state: Res<State<GameState>>,
     mut next_state: ResMut<NextState<GameState>>,
     mut pause_state: ResMut<PauseState>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::Escape) {
+    if actions.just_pressed(PlayerAction::Pause) {
         match current_state.get() {
             GameState::InGame => {
                 // Set flags to indicate we're transitioning to pause
@@ -112,10 +113,18 @@ pub fn handle_pause_input(
     mut pause_state: ResMut<PauseState>,
     mut app_exit_events: EventWriter<AppExit>,
     mut commands: Commands,
-    query: Query<Entity, Without<Camera>>,
+    query: Query<Entity, (Without<Camera>, Without<Handle<AudioSource>>)>,
     camera_query: Query<Entity, With<Camera>>,
     window_query: Query<Entity, With<Window>>,
+    actions: Res<ActionState>,
 ) {
+    // Back resumes the game like the Resume button
+    if actions.just_pressed(PlayerAction::Back) {
+        pause_state.transitioning_to_pause = false;
+        next_state.set(GameState::InGame);
+        return;
+    }
+
     for (interaction, mut color, button) in button_query.iter_mut() {
         match *interaction {
             Interaction::Pressed => {
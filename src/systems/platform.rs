@@ -0,0 +1,140 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::components::{Enemy, GameSettings, Platform, PlatformEdge, PlatformPiece, PlatformTilt, Player};
+use crate::resources::{Arena, GameTuning, PieceShape};
+
+/// How far the cosmetic rim sticks out past each piece.
+const EDGE_OVERHANG: f32 = 0.1;
+const EDGE_THICKNESS: f32 = 0.2;
+
+/// Collider matching a piece's mesh.
+fn piece_collider(shape: PieceShape, thickness: f32) -> Collider {
+    let half_height = thickness / 2.0;
+    match shape {
+        PieceShape::Disc { radius, sides: None } => Collider::cylinder(half_height, radius),
+        PieceShape::Disc { radius, sides: Some(sides) } => {
+            // Corners where the polygon mesh puts them, starting on +X
+            let corners = (0..sides)
+                .flat_map(|corner| {
+                    let angle = corner as f32 * std::f32::consts::TAU / sides as f32;
+                    let (x, z) = (angle.cos() * radius, angle.sin() * radius);
+                    [Vec3::new(x, half_height, z), Vec3::new(x, -half_height, z)]
+                })
+                .collect::<Vec<_>>();
+            Collider::convex_hull(&corners).unwrap_or_else(|| Collider::cylinder(half_height, radius))
+        }
+        PieceShape::Slab { half_extents } => Collider::cuboid(half_extents.x, half_height, half_extents.y),
+    }
+}
+
+/// The same shape grown outwards by `amount`.
+fn grown(shape: PieceShape, amount: f32) -> PieceShape {
+    match shape {
+        PieceShape::Disc { radius, sides } => PieceShape::Disc { radius: radius + amount, sides },
+        PieceShape::Slab { half_extents } => PieceShape::Slab { half_extents: half_extents + amount },
+    }
+}
+
+/// Spawns the platform colliders for the arena in play, on a pivot when the
+/// tilting platform is picked. Visuals are attached by the presentation layer.
+pub fn spawn_platform(mut commands: Commands, arena: Res<Arena>, settings: Res<GameSettings>) {
+    let mut platform = commands.spawn((
+        SpatialBundle::from_transform(Transform::from_xyz(0.0, arena.height, 0.0)),
+        Platform,
+    ));
+    if settings.tilting {
+        platform.insert((RigidBody::KinematicPositionBased, PlatformTilt::default()));
+    } else {
+        platform.insert(RigidBody::Fixed);
+    }
+
+    platform.with_children(|parent| {
+        for piece in arena.pieces() {
+            let transform = Transform::from_xyz(piece.offset.x, 0.0, piece.offset.y)
+                .with_rotation(Quat::from_rotation_y(piece.yaw));
+
+            parent.spawn((
+                SpatialBundle::from_transform(transform),
+                PlatformPiece {
+                    shape: piece.shape,
+                    thickness: arena.thickness,
+                },
+                piece_collider(piece.shape, arena.thickness),
+                CollisionGroups::new(Group::GROUP_1, Group::GROUP_1 | Group::GROUP_2),
+            ));
+
+            // Edge highlight
+            let edge = grown(piece.shape, EDGE_OVERHANG);
+            parent.spawn((
+                SpatialBundle::from_transform(
+                    transform.with_translation(transform.translation + Vec3::Y * (arena.thickness / 2.0 + 0.1)),
+                ),
+                PlatformEdge {
+                    shape: edge,
+                    thickness: EDGE_THICKNESS,
+                },
+                piece_collider(edge, EDGE_THICKNESS),
+                CollisionGroups::new(Group::GROUP_1, Group::GROUP_1 | Group::GROUP_2),
+            ));
+        }
+    });
+}
+
+/// Whether `duck` stands on the platform, and so weighs on it.
+fn on_platform(arena: &Arena, duck: &Transform) -> bool {
+    arena.is_standing(duck.translation) && arena.edge_distance(duck.translation.xz()) >= 0.0
+}
+
+/// Tips a tilting platform toward where the ducks stand. Every duck on it
+/// pushes its side down by its weight times its distance from the pivot; a
+/// spring pulls the platform back level and damping settles it. Ducks on a
+/// tilted platform slide downhill.
+pub fn tilt_platform(
+    mut platform_query: Query<(&mut Transform, &mut PlatformTilt), With<Platform>>,
+    mut duck_query: Query<(&Transform, &mut Velocity), (Or<(With<Player>, With<Enemy>)>, Without<Platform>)>,
+    mut arena: ResMut<Arena>,
+    time: Res<Time>,
+    tuning: Res<GameTuning>,
+) {
+    let tuning = &tuning.tilt;
+    let dt = time.delta_seconds();
+
+    for (mut transform, mut tilt) in platform_query.iter_mut() {
+        let pivot = transform.translation.xz();
+        // Weight on +Z tips about +X, weight on +X tips about -Z
+        let torque = duck_query
+            .iter()
+            .filter(|(duck, _)| on_platform(&arena, duck))
+            .map(|(duck, _)| {
+                let offset = duck.translation.xz() - pivot;
+                Vec2::new(offset.y, -offset.x) * duck.scale.x.powi(3)
+            })
+            .sum::<Vec2>();
+
+        let acceleration = torque * tuning.weight_torque
+            - tilt.angle * tuning.stiffness
+            - tilt.angular_velocity * tuning.damping;
+        tilt.angular_velocity += acceleration * dt;
+        let angle = tilt.angle + tilt.angular_velocity * dt;
+
+        // Stop at the limit instead of bouncing off it
+        let max_angle = tuning.max_angle.to_radians();
+        tilt.angle = angle.clamp_length_max(max_angle);
+        if angle.length() > max_angle {
+            let outward = tilt.angle.normalize_or_zero();
+            let speed = tilt.angular_velocity.dot(outward).max(0.0);
+            tilt.angular_velocity -= outward * speed;
+        }
+        arena.tilt = tilt.angle;
+        transform.rotation = arena.tilt_rotation();
+
+        // The platform's up tips toward the low side
+        let downhill = (transform.rotation * Vec3::Y).xz();
+        for (duck, mut velocity) in duck_query.iter_mut() {
+            if on_platform(&arena, duck) {
+                velocity.linvel += Vec3::new(downhill.x, 0.0, downhill.y) * tuning.slide_acceleration * dt;
+            }
+        }
+    }
+}
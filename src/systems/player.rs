@@ -1,77 +1,63 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
-use crate::components::{Player, EnergyBoost, PLATFORM_HEIGHT, BearScore, DuckParams, spawn_duck};
+use crate::components::{
+    Player, PlayerInput, EnergyBoost, Contestant, DuckParams, spawn_duck,
+    GameSettings, Health, HitStun, LastHitBy, DuckKnockedOut, MAX_LOCAL_PLAYERS, PLAYER_COLORS,
+};
+use crate::resources::{ActionState, Arena, GameTuning, SoundEffect};
 use crate::states::GameState;
 
-const FALL_THRESHOLD: f32 = -5.0;
-const SPAWN_POSITION: Vec3 = Vec3::new(0.0, PLATFORM_HEIGHT + 2.0, 0.0);
-
-// Physics constants
-const BASE_MOVEMENT_FORCE: f32 = 25.0;
-const MAX_SPEED: f32 = 8.0;
-const BOOST_MAX_SPEED: f32 = 15.0;  // Higher max speed when boosting
-/// Friction constant (currently unused but potentially useful for future physics tweaks)
-#[allow(dead_code)]
-const FRICTION: f32 = 0.9;
-
-/// Push force constant (currently unused but potentially useful for future collision mechanics)
-#[allow(dead_code)]
-const PUSH_FORCE: f32 = 10.0;
-const FALL_ACCELERATION: f32 = 30.0;  // Additional downward force when falling
-
 pub fn player_movement(
-    mut player_query: Query<(&mut Transform, &mut Velocity, &EnergyBoost), With<Player>>,
-    keyboard_input: Res<Input<KeyCode>>,
+    mut player_query: Query<(&mut Velocity, &EnergyBoost, &PlayerInput, &HitStun), With<Player>>,
     time: Res<Time>,
+    tuning: Res<GameTuning>,
 ) {
-    for (_transform, mut velocity, boost) in player_query.iter_mut() {
-        let mut direction = Vec3::ZERO;
-
-        // Diagonal movement handling with normalized speed
-        if !boost.is_boosting {
-            if keyboard_input.pressed(KeyCode::W) {
-                direction.z -= 1.0;
-            }
-            if keyboard_input.pressed(KeyCode::S) {
-                direction.z += 1.0;
-            }
-            if keyboard_input.pressed(KeyCode::A) {
-                direction.x -= 1.0;
-            }
-            if keyboard_input.pressed(KeyCode::D) {
-                direction.x += 1.0;
-            }
+    let windup_brake = tuning.boost.windup_brake;
+    let tuning = &tuning.player;
+    for (mut velocity, boost, input, stun) in player_query.iter_mut() {
+        // Stunned ducks slide wherever the hit sent them
+        if stun.is_stunned() {
+            continue;
         }
 
-        if boost.is_boosting {
-            // Always push forward when boosting (negative Z direction)
-            direction = Vec3::new(0.0, 0.0, -1.0);
+        // Brake while the boost is telegraphed, before pushing off
+        if boost.is_winding_up() {
+            velocity.linvel *= (-windup_brake * time.delta_seconds()).exp();
+            continue;
         }
 
+        // Diagonal movement handling with normalized speed; a boost pushes
+        // along the direction it was locked to when it started
+        let mut direction = if boost.is_boosting {
+            boost.direction
+        } else {
+            Vec3::new(input.movement.x, 0.0, -input.movement.y)
+        };
+
         if direction != Vec3::ZERO {
             // Normalize diagonal movement
             direction = direction.normalize();
             
             // Smooth acceleration
             let acceleration = if boost.is_boosting {
-                BASE_MOVEMENT_FORCE * 5.0  // Enhanced boost acceleration
+                tuning.movement_force * tuning.boost_acceleration  // Enhanced boost acceleration
             } else {
-                BASE_MOVEMENT_FORCE * 1.2  // Smoother base acceleration
+                tuning.movement_force * tuning.movement_acceleration  // Smoother base acceleration
             };
             
             velocity.linvel += direction * acceleration * time.delta_seconds();
         } else {
             // Gradual deceleration when no input
-            velocity.linvel *= 0.9;
+            velocity.linvel *= (-tuning.idle_deceleration_rate * time.delta_seconds()).exp();
         }
 
         // Clamp maximum speed, with higher limit when boosting
         let speed = velocity.linvel.length();
         let max_speed = if boost.is_boosting {
-            BOOST_MAX_SPEED * 1.2  // Slightly higher boost speed
+            tuning.boost_max_speed
         } else {
-            MAX_SPEED
+            tuning.max_speed
         };
         
         if speed > max_speed {
@@ -80,93 +66,110 @@ pub fn player_movement(
     }
 }
 
-pub fn handle_collisions(
-    mut collision_events: EventReader<CollisionEvent>,
-    mut player_query: Query<(&Transform, &mut Velocity), With<Player>>,
-    other_query: Query<&Transform, Without<Player>>,
-) {
-    for collision_event in collision_events.read() {
-        if let CollisionEvent::Started(e1, e2, _) = collision_event {
-            // Get the player and other entity's transform and velocity
-            let (player_entity, other_entity) = if let Ok(_) = player_query.get_mut(*e1) {
-                (*e1, *e2)
-            } else if let Ok(_) = player_query.get_mut(*e2) {
-                (*e2, *e1)
-            } else {
-                continue;
-            };
-
-            if let (Ok((player_transform, mut player_velocity)), Ok(other_transform)) = (
-                player_query.get_mut(player_entity),
-                other_query.get(other_entity),
-            ) {
-                // Calculate push direction
-                let direction = (player_transform.translation - other_transform.translation).normalize();
-                player_velocity.linvel += direction * PUSH_FORCE;
-            }
-        }
-    }
-}
-
 pub fn check_fall(
-    mut player_query: Query<(&mut Transform, &mut Velocity, &mut BearScore), With<Player>>,
+    mut commands: Commands,
+    mut player_query: Query<(
+        Entity,
+        &Player,
+        &mut Transform,
+        &mut Velocity,
+        &mut Contestant,
+        &mut Health,
+        &mut LastHitBy,
+    )>,
+    arena: Res<Arena>,
     time: Res<Time>,
+    tuning: Res<GameTuning>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut sounds: EventWriter<SoundEffect>,
+    mut knockouts: EventWriter<DuckKnockedOut>,
 ) {
-    for (mut transform, mut velocity, mut score) in player_query.iter_mut() {
+    for (entity, player, mut transform, mut velocity, mut contestant, mut health, mut last_hit) in
+        player_query.iter_mut()
+    {
+        if contestant.is_eliminated() {
+            continue;
+        }
+
         // Apply extra downward force when falling
-        if transform.translation.y < PLATFORM_HEIGHT {
-            velocity.linvel.y -= FALL_ACCELERATION * time.delta_seconds();
+        if arena.height_above_platform(transform.translation) < 0.0 {
+            velocity.linvel.y -= tuning.player.fall_acceleration * time.delta_seconds();
         }
 
-        if transform.translation.y < FALL_THRESHOLD {
-            // Deduct points
-            score.value -= 1;
-            
-            // Check if player has lost all points
-            if score.value <= 0 {
-                // Trigger game over
-                next_state.set(GameState::GameOver);
-                return;
+        if transform.translation.y < arena.kill_plane {
+            contestant.record_fall();
+            sounds.send(SoundEffect::Fall);
+            if let Some(attacker) = last_hit.take_credit().filter(|attacker| *attacker != entity) {
+                knockouts.send(DuckKnockedOut { victim: entity, attacker });
             }
-            
-            // Reset player position
-            transform.translation = SPAWN_POSITION;
             velocity.linvel = Vec3::ZERO;
             velocity.angvel = Vec3::ZERO;
+
+            if contestant.is_eliminated() {
+                // Park the duck below the platform while the others play on
+                transform.translation.y = arena.kill_plane;
+                commands
+                    .entity(entity)
+                    .insert((RigidBody::Fixed, Visibility::Hidden));
+                continue;
+            }
+
+            // Reset player position
+            transform.translation = arena.player_spawn(player.index);
+            health.restore();
         }
     }
+
+    // The match is over once every human duck is out
+    let all_players_out = !player_query.is_empty()
+        && player_query
+            .iter()
+            .all(|(_, _, _, _, contestant, _, _)| contestant.is_eliminated());
+    if all_players_out {
+        next_state.set(GameState::GameOver);
+    }
 }
 
+/// Spawns one duck per local player, each reading its own input device.
 pub fn spawn_player(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    settings: Res<GameSettings>,
+    tuning: Res<GameTuning>,
+    arena: Res<Arena>,
 ) {
-    let params = DuckParams {
-        body_radius: 0.5,
-        head_radius: 0.3,
-        bill_length: 0.4,
-        body_offset: Vec3::new(0.0, 0.0, 0.0),
-        head_offset: Vec3::new(0.0, 0.6, 0.0),
-        bill_offset: Vec3::new(0.2, 0.0, 0.0),
-        base_color: Color::rgb(0.2, 0.7, 0.2),
-        bill_color: Color::rgb(0.8, 0.6, 0.0),
-        position: SPAWN_POSITION,
-        is_player: true,
-    };
-
-    let entity = spawn_duck(
-        &mut commands,
-        &mut meshes,
-        &mut materials,
-        params,
-    );
-
-    commands.entity(entity).insert((
-        Player::new(8.0),
-        BearScore::new("Player".to_string()),
-        EnergyBoost::default(),
-        crate::components::ActivePowerUp::default(),
-    ));
+    let player_count = settings.player_count.clamp(1, MAX_LOCAL_PLAYERS);
+
+    for index in 0..player_count {
+        let params = DuckParams {
+            body_radius: 0.5,
+            head_radius: 0.3,
+            bill_length: 0.4,
+            body_offset: Vec3::new(0.0, 0.0, 0.0),
+            head_offset: Vec3::new(0.0, 0.6, 0.0),
+            bill_offset: Vec3::new(0.2, 0.0, 0.0),
+            base_color: PLAYER_COLORS[index],
+            bill_color: Color::rgb(0.8, 0.6, 0.0),
+            position: arena.player_spawn(index),
+            is_player: true,
+        };
+
+        let entity = spawn_duck(&mut commands, params);
+
+        let name = if player_count == 1 {
+            "Player".to_string()
+        } else {
+            format!("Player {}", index + 1)
+        };
+
+        commands.entity(entity).insert((
+            Player::new(index),
+            PlayerInput::default(),
+            settings.bindings.source_for_player(index, player_count),
+            ActionState::default(),
+            Contestant::new(name, tuning.rules.starting_lives),
+            EnergyBoost::new(&tuning.boost),
+            Health::new(tuning.health.player_health, &tuning.health),
+            crate::components::ActivePowerUp::default(),
+        ));
+    }
 }
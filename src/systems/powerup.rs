@@ -8,6 +8,7 @@ use crate::components::{
     ActivePowerUp,
     Enemy,
 };
+use crate::resources::{Arena, GameRng, RngStream, SoundEffect};
 
 #[derive(Component)]
 pub struct PowerUpCoin {
@@ -15,6 +16,15 @@ pub struct PowerUpCoin {
     pub lifetime: Timer,
 }
 
+/// Countdown until the next coin spawn; `None` until the first roll of a match.
+#[derive(Resource, Default)]
+pub struct PowerUpSpawnTimer(pub Option<Timer>);
+
+/// Clears the coin spawn countdown so every match starts from the same state.
+pub fn reset_powerup_spawn_timer(mut spawn_timer: ResMut<PowerUpSpawnTimer>) {
+    spawn_timer.0 = None;
+}
+
 /// Applies active powerup effects to the player.
 pub fn apply_powerup_effects(
     time: Res<Time>,
@@ -52,45 +62,31 @@ pub fn apply_powerup_effects(
 pub fn spawn_random_powerup_coin(
     mut commands: Commands,
     time: Res<Time>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    mut spawn_timer: Local<Option<Timer>>,
+    mut spawn_timer: ResMut<PowerUpSpawnTimer>,
+    mut game_rng: ResMut<GameRng>,
     powerup_query: Query<&PowerUpCoin>,
+    arena: Res<Arena>,
 ) {
+    let rng = game_rng.stream(RngStream::PowerUps);
     let existing_powerups = powerup_query.iter().count();
     // Initialize spawn timer if it's not already set.
-    if spawn_timer.is_none() {
-        let duration = rand::thread_rng().gen_range(2.0..=6.0);
-        *spawn_timer = Some(Timer::from_seconds(duration, TimerMode::Once));
+    if spawn_timer.0.is_none() {
+        let duration = rng.gen_range(2.0..=6.0);
+        spawn_timer.0 = Some(Timer::from_seconds(duration, TimerMode::Once));
     }
 
-    if let Some(timer) = spawn_timer.as_mut() {
+    if let Some(timer) = spawn_timer.0.as_mut() {
         timer.tick(time.delta());
         if timer.finished() && existing_powerups < 2 {
-            let mut rng = rand::thread_rng();
             let power_type = if rng.gen_bool(0.5) {
                 PowerUpType::Grow
             } else {
                 PowerUpType::Shrink
             };
-            // Spawn coin at a random position above the platform.
-            let x = rng.gen_range(-5.0..5.0);
-            let y = rng.gen_range(-5.0..5.0);
+            // Spawn coin at a random position above the platform, away from the edge.
+            let spot = arena.random_point(rng, arena.depth() * 0.5);
             commands.spawn((
-                PbrBundle {
-                    mesh: meshes.add(shape::Box::new(0.5, 0.5, 0.5).into()),
-                    material: materials.add(StandardMaterial {
-                        base_color: match power_type {
-                            PowerUpType::Grow => Color::rgb(1.0, 0.8, 0.0),
-                            PowerUpType::Shrink => Color::rgb(0.0, 0.8, 1.0),
-                        },
-                        metallic: 1.0,
-                        perceptual_roughness: 0.1,
-                        ..default()
-                    }),
-                    transform: Transform::from_xyz(x, 6.0, y),
-                    ..default()
-                },
+                SpatialBundle::from_transform(Transform::from_xyz(spot.x, arena.level_at(spot) + 1.0, spot.y)),
                 PowerUpCoin {
                     power_type,
                     lifetime: Timer::from_seconds(10.0, TimerMode::Once),
@@ -105,7 +101,7 @@ pub fn spawn_random_powerup_coin(
 
             // Reset timer with a new random duration.
             let duration = rng.gen_range(4.0..=8.0);
-            *spawn_timer = Some(Timer::from_seconds(duration, TimerMode::Once));
+            spawn_timer.0 = Some(Timer::from_seconds(duration, TimerMode::Once));
         }
     }
 }
@@ -117,9 +113,10 @@ pub fn collect_powerup_coin(
     enemy_query: Query<Entity, With<Enemy>>,
     coin_query: Query<(Entity, &PowerUpCoin)>,
     mut collision_events: EventReader<CollisionEvent>,
+    mut sounds: EventWriter<SoundEffect>,
 ) {
     let mut coins_to_despawn = Vec::new();
-    let mut powerup_to_apply = None;
+    let mut powerups_to_apply = Vec::new();
 
     for collision_event in collision_events.read() {
         if let CollisionEvent::Started(e1, e2, _) = collision_event {
@@ -135,7 +132,7 @@ pub fn collect_powerup_coin(
             if enemy_query.get(other_entity).is_ok() || player_query.get(other_entity).is_ok() {
                 coins_to_despawn.push(coin_entity);
                 if player_query.get(other_entity).is_ok() {
-                    powerup_to_apply = Some(coin.power_type);
+                    powerups_to_apply.push((other_entity, coin.power_type));
                 }
             }
         }
@@ -143,28 +140,34 @@ pub fn collect_powerup_coin(
 
     for coin_entity in coins_to_despawn {
         commands.entity(coin_entity).despawn();
+        sounds.send(SoundEffect::CoinPickup);
     }
 
-    if let Some(power_type) = powerup_to_apply {
-        let mut active_powerup = player_query.single_mut();
-        apply_powerup_effect(&mut active_powerup, power_type);
+    // Each coin goes to the player who touched it
+    for (player_entity, power_type) in powerups_to_apply {
+        if let Ok(mut active_powerup) = player_query.get_mut(player_entity) {
+            if apply_powerup_effect(&mut active_powerup, power_type) {
+                sounds.send(match power_type {
+                    PowerUpType::Grow => SoundEffect::Grow,
+                    PowerUpType::Shrink => SoundEffect::Shrink,
+                });
+            }
+        }
     }
 }
 
 /// Applies the powerup effect to the player's active powerup state.
-fn apply_powerup_effect(active_powerup: &mut ActivePowerUp, power_type: PowerUpType) {
-    match power_type {
-        PowerUpType::Grow => {
-            if active_powerup.grow.is_none() {
-                active_powerup.grow = Some(PowerUp::new(PowerUpType::Grow, 6.0));
-            }
-        },
-        PowerUpType::Shrink => {
-            if active_powerup.shrink.is_none() {
-                active_powerup.shrink = Some(PowerUp::new(PowerUpType::Shrink, 6.0));
-            }
-        },
+/// Returns false when the same effect was already running.
+fn apply_powerup_effect(active_powerup: &mut ActivePowerUp, power_type: PowerUpType) -> bool {
+    let slot = match power_type {
+        PowerUpType::Grow => &mut active_powerup.grow,
+        PowerUpType::Shrink => &mut active_powerup.shrink,
+    };
+    if slot.is_some() {
+        return false;
     }
+    *slot = Some(PowerUp::new(power_type, 6.0));
+    true
 }
 
 /// Removes expired powerup coins.
@@ -0,0 +1,415 @@
+use bevy::{prelude::*, ui::RelativeCursorPosition, window::WindowMode};
+
+use crate::{
+    components::{
+        GameSettings, MenuButtonAction, SettingsControl, SettingsMenu, SettingsSliderFill,
+        SettingsValueText,
+    },
+    resources::{ActionState, PlayerAction},
+    states::GameState,
+    styles::*,
+    systems::{spawn_menu_button, MenuFocus},
+};
+
+/// Step a focused volume slider moves per left/right press.
+const SLIDER_STEP: f32 = 0.1;
+
+const SETTINGS_ROW_HEIGHT: f32 = 36.0;
+const SETTINGS_LABEL_WIDTH: f32 = 200.0;
+const SETTINGS_CONTROL_WIDTH: f32 = 160.0;
+
+/// Window modes the settings screen cycles through.
+const WINDOW_MODES: [WindowMode; 3] = [
+    WindowMode::Windowed,
+    WindowMode::BorderlessFullscreen,
+    WindowMode::Fullscreen,
+];
+
+/// Actions whose primary key can be rebound from the settings screen.
+const REBINDABLE_ACTIONS: [PlayerAction; 6] = [
+    PlayerAction::MoveForward,
+    PlayerAction::MoveBack,
+    PlayerAction::MoveLeft,
+    PlayerAction::MoveRight,
+    PlayerAction::Boost,
+    PlayerAction::Pause,
+];
+
+/// The action waiting for a new key, if the player has clicked a binding.
+#[derive(Resource, Default)]
+pub struct PendingRebind(pub Option<PlayerAction>);
+
+pub fn spawn_settings_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    // Camera
+    commands.spawn(Camera2dBundle::default());
+
+    let settings_menu = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                },
+                background_color: Color::NONE.into(), // Transparent background to show shader
+                ..default()
+            },
+            SettingsMenu,
+        ))
+        .id();
+
+    // Title
+    commands.spawn(NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Row,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            width: Val::Px(300.0),
+            height: Val::Px(120.0),
+            ..default()
+        },
+        ..default()
+    }).with_children(|parent| {
+        parent.spawn(TextBundle {
+            text: Text::from_section("Settings", get_title_text_style(&asset_server)),
+            ..default()
+        });
+    }).set_parent(settings_menu);
+
+    // Two columns: audio, display and gameplay on the left, key bindings on the right
+    let columns = commands.spawn(NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(40.0),
+            ..default()
+        },
+        ..default()
+    }).set_parent(settings_menu).id();
+
+    let general = spawn_settings_column(&mut commands, columns);
+    spawn_slider_row(&mut commands, &asset_server, "Master Volume", SettingsControl::MasterVolume, general);
+    spawn_slider_row(&mut commands, &asset_server, "Music Volume", SettingsControl::MusicVolume, general);
+    spawn_slider_row(&mut commands, &asset_server, "Effects Volume", SettingsControl::SfxVolume, general);
+    spawn_button_row(&mut commands, &asset_server, "Window", SettingsControl::WindowMode, general);
+    spawn_button_row(&mut commands, &asset_server, "VSync", SettingsControl::Vsync, general);
+    spawn_button_row(&mut commands, &asset_server, "Shadows", SettingsControl::Shadows, general);
+    spawn_button_row(&mut commands, &asset_server, "Difficulty", SettingsControl::Difficulty, general);
+
+    let bindings = spawn_settings_column(&mut commands, columns);
+    for action in REBINDABLE_ACTIONS {
+        spawn_button_row(
+            &mut commands,
+            &asset_server,
+            action_label(action),
+            SettingsControl::Binding(action),
+            bindings,
+        );
+    }
+
+    // Buttons
+    spawn_menu_button(&mut commands, &asset_server, "Back", MenuButtonAction::Back, settings_menu);
+}
+
+fn spawn_settings_column(commands: &mut Commands, parent: Entity) -> Entity {
+    commands.spawn(NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(8.0),
+            ..default()
+        },
+        ..default()
+    }).set_parent(parent).id()
+}
+
+/// A labelled row; returns the row so the caller can add its control.
+fn spawn_settings_row(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    label: &str,
+    parent: Entity,
+) -> Entity {
+    commands.spawn(NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            height: Val::Px(SETTINGS_ROW_HEIGHT),
+            column_gap: Val::Px(10.0),
+            ..default()
+        },
+        ..default()
+    }).with_children(|parent| {
+        parent.spawn(TextBundle {
+            text: Text::from_section(label, get_settings_text_style(asset_server)),
+            style: Style {
+                width: Val::Px(SETTINGS_LABEL_WIDTH),
+                ..default()
+            },
+            ..default()
+        });
+    }).set_parent(parent).id()
+}
+
+/// A button showing the setting's value; pressing it toggles, cycles or rebinds.
+fn spawn_button_row(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    label: &str,
+    control: SettingsControl,
+    parent: Entity,
+) {
+    let row = spawn_settings_row(commands, asset_server, label, parent);
+    commands.spawn((
+        ButtonBundle {
+            style: Style {
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                width: Val::Px(SETTINGS_CONTROL_WIDTH),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            background_color: NORMAL_BUTTON_COLOR.into(),
+            ..default()
+        },
+        control,
+    )).with_children(|parent| {
+        parent.spawn((
+            TextBundle::from_section("", get_settings_text_style(asset_server)),
+            SettingsValueText(control),
+        ));
+    }).set_parent(row);
+}
+
+/// A volume slider: click or drag along it with the mouse, or focus it and press left/right.
+fn spawn_slider_row(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    label: &str,
+    control: SettingsControl,
+    parent: Entity,
+) {
+    let row = spawn_settings_row(commands, asset_server, label, parent);
+    commands.spawn((
+        ButtonBundle {
+            style: Style {
+                width: Val::Px(SETTINGS_CONTROL_WIDTH),
+                height: Val::Px(SETTINGS_ROW_HEIGHT / 2.0),
+                ..default()
+            },
+            background_color: NORMAL_BUTTON_COLOR.into(),
+            ..default()
+        },
+        RelativeCursorPosition::default(),
+        control,
+    )).with_children(|parent| {
+        parent.spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(0.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                background_color: Color::rgb(1.0, 0.9, 0.2).into(),
+                ..default()
+            },
+            SettingsSliderFill(control),
+        ));
+    }).set_parent(row);
+
+    commands.spawn((
+        TextBundle::from_section("", get_settings_text_style(asset_server)),
+        SettingsValueText(control),
+    )).set_parent(row);
+}
+
+fn get_settings_text_style(asset_server: &AssetServer) -> TextStyle {
+    TextStyle {
+        font_size: 24.0,
+        ..get_button_text_style(asset_server)
+    }
+}
+
+fn action_label(action: PlayerAction) -> &'static str {
+    match action {
+        PlayerAction::MoveForward => "Move Forward",
+        PlayerAction::MoveBack => "Move Back",
+        PlayerAction::MoveLeft => "Move Left",
+        PlayerAction::MoveRight => "Move Right",
+        PlayerAction::Boost => "Boost",
+        PlayerAction::Pause => "Pause",
+        PlayerAction::Confirm => "Confirm",
+        PlayerAction::Back => "Back",
+    }
+}
+
+fn window_mode_label(mode: WindowMode) -> &'static str {
+    match mode {
+        WindowMode::Windowed => "Windowed",
+        WindowMode::BorderlessFullscreen => "Borderless",
+        WindowMode::SizedFullscreen | WindowMode::Fullscreen => "Fullscreen",
+    }
+}
+
+fn volume_mut(settings: &mut GameSettings, control: SettingsControl) -> Option<&mut f32> {
+    match control {
+        SettingsControl::MasterVolume => Some(&mut settings.master_volume),
+        SettingsControl::MusicVolume => Some(&mut settings.music_volume),
+        SettingsControl::SfxVolume => Some(&mut settings.sfx_volume),
+        _ => None,
+    }
+}
+
+/// Returns to the main menu on Back, unless Back is cancelling a key rebind.
+pub fn handle_settings_back(
+    actions: Res<ActionState>,
+    pending: Res<PendingRebind>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if pending.0.is_none() && actions.just_pressed(PlayerAction::Back) {
+        next_state.set(GameState::MainMenu);
+    }
+}
+
+/// Waits for the next key after a binding was clicked. Escape cancels.
+/// Runs before `handle_settings_controls` so the key that started the rebind isn't captured.
+pub fn capture_rebind_key(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut pending: ResMut<PendingRebind>,
+    mut settings: ResMut<GameSettings>,
+) {
+    let Some(action) = pending.0 else {
+        return;
+    };
+
+    if let Some(key) = keyboard_input.get_just_pressed().next() {
+        if *key != KeyCode::Escape {
+            settings.bindings.rebind(action, *key);
+        }
+        pending.0 = None;
+    }
+}
+
+/// Applies clicks, drags and focused left/right presses on the settings controls.
+pub fn handle_settings_controls(
+    mut control_query: Query<
+        (
+            Entity,
+            Ref<Interaction>,
+            &mut BackgroundColor,
+            &SettingsControl,
+            Option<&RelativeCursorPosition>,
+        ),
+        With<Button>,
+    >,
+    actions: Res<ActionState>,
+    focus: Res<MenuFocus>,
+    mut pending: ResMut<PendingRebind>,
+    mut settings: ResMut<GameSettings>,
+) {
+    for (entity, interaction, mut color, control, cursor) in control_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => *color = PRESSED_BUTTON_COLOR.into(),
+            Interaction::Hovered => *color = HOVERED_BUTTON_COLOR.into(),
+            Interaction::None => {}
+        }
+
+        if let Some(cursor) = cursor {
+            // Sliders follow the mouse while held; a Confirm press from focus navigation is ignored
+            let mouse_value = cursor
+                .normalized
+                .filter(|_| *interaction == Interaction::Pressed && focus.pressed != Some(entity))
+                .map(|position| position.x.clamp(0.0, 1.0));
+
+            let mut step = 0.0;
+            if focus.focused == Some(entity) {
+                if actions.just_pressed(PlayerAction::MoveRight) {
+                    step += SLIDER_STEP;
+                }
+                if actions.just_pressed(PlayerAction::MoveLeft) {
+                    step -= SLIDER_STEP;
+                }
+            }
+
+            let current = volume_mut(settings.bypass_change_detection(), *control).map(|volume| *volume);
+            if let Some(current) = current {
+                let value = mouse_value.unwrap_or(current) + step;
+                let value = (value.clamp(0.0, 1.0) * 100.0).round() / 100.0;
+                if value != current {
+                    if let Some(volume) = volume_mut(&mut settings, *control) {
+                        *volume = value;
+                    }
+                }
+            }
+            continue;
+        }
+
+        // Buttons act once per press
+        if !(interaction.is_changed() && *interaction == Interaction::Pressed) {
+            continue;
+        }
+        match *control {
+            SettingsControl::WindowMode => {
+                let index = WINDOW_MODES
+                    .iter()
+                    .position(|mode| *mode == settings.window_mode)
+                    .unwrap_or(0);
+                settings.window_mode = WINDOW_MODES[(index + 1) % WINDOW_MODES.len()];
+            }
+            SettingsControl::Vsync => settings.vsync = !settings.vsync,
+            SettingsControl::Shadows => settings.shadows = !settings.shadows,
+            SettingsControl::Difficulty => settings.difficulty = settings.difficulty.next(),
+            SettingsControl::Binding(action) => pending.0 = Some(action),
+            SettingsControl::MasterVolume
+            | SettingsControl::MusicVolume
+            | SettingsControl::SfxVolume => {}
+        }
+    }
+}
+
+/// Shows the current value of every control on the settings screen.
+pub fn update_settings_values(
+    settings: Res<GameSettings>,
+    pending: Res<PendingRebind>,
+    mut text_query: Query<(&mut Text, &SettingsValueText)>,
+    mut fill_query: Query<(&mut Style, &SettingsSliderFill)>,
+) {
+    for (mut text, value_text) in text_query.iter_mut() {
+        let value = match value_text.0 {
+            SettingsControl::MasterVolume => format!("{:.0}%", settings.master_volume * 100.0),
+            SettingsControl::MusicVolume => format!("{:.0}%", settings.music_volume * 100.0),
+            SettingsControl::SfxVolume => format!("{:.0}%", settings.sfx_volume * 100.0),
+            SettingsControl::WindowMode => window_mode_label(settings.window_mode).to_string(),
+            SettingsControl::Vsync => on_off(settings.vsync),
+            SettingsControl::Shadows => on_off(settings.shadows),
+            SettingsControl::Difficulty => settings.difficulty.label().to_string(),
+            SettingsControl::Binding(action) if pending.0 == Some(action) => "Press a key".to_string(),
+            SettingsControl::Binding(action) => settings
+                .bindings
+                .keys_for(action)
+                .iter()
+                .map(|key| format!("{:?}", key))
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+
+    for (mut style, fill) in fill_query.iter_mut() {
+        let value = match fill.0 {
+            SettingsControl::MasterVolume => settings.master_volume,
+            SettingsControl::MusicVolume => settings.music_volume,
+            SettingsControl::SfxVolume => settings.sfx_volume,
+            _ => continue,
+        };
+        style.width = Val::Percent(value * 100.0);
+    }
+}
+
+fn on_off(value: bool) -> String {
+    if value { "On" } else { "Off" }.to_string()
+}
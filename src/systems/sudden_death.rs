@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+
+use crate::components::{Contestant, GameSettings, Platform, SuddenDeathText};
+use crate::resources::{Arena, GameTuning};
+
+/// Seconds before the first stage the HUD starts counting down.
+const COUNTDOWN_WARNING: f32 = 10.0;
+/// Height above the platform surface the next edge is marked at.
+const OUTLINE_LIFT: f32 = 0.25;
+
+/// Where sudden death is between stages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShrinkPhase {
+    /// Waiting for the match to drag on, or for the next stage
+    #[default]
+    Waiting,
+    /// The next edge is marked; the platform shrinks when the timer runs out
+    Warning,
+    /// The platform is shrinking to the marked edge
+    Shrinking,
+    /// The platform is as small as it gets
+    Done,
+}
+
+/// Shrinks the platform in telegraphed stages when sudden death is on.
+#[derive(Resource, Debug)]
+pub struct SuddenDeath {
+    /// Stages started so far
+    pub stage: u32,
+    pub phase: ShrinkPhase,
+    pub timer: Timer,
+    /// Arena scale the current stage shrinks from and to
+    pub from: f32,
+    pub to: f32,
+}
+
+impl Default for SuddenDeath {
+    fn default() -> Self {
+        Self {
+            stage: 0,
+            phase: ShrinkPhase::Waiting,
+            timer: Timer::from_seconds(0.0, TimerMode::Once),
+            from: 1.0,
+            to: 1.0,
+        }
+    }
+}
+
+pub fn reset_sudden_death(mut sudden_death: ResMut<SuddenDeath>, tuning: Res<GameTuning>) {
+    *sudden_death = SuddenDeath {
+        timer: Timer::from_seconds(tuning.sudden_death.start_after, TimerMode::Once),
+        ..default()
+    };
+}
+
+/// Steps through wait, warning and shrink for each stage, scaling the
+/// arena geometry and the platform entity together so the colliders, the
+/// meshes and every edge check agree. The first stage comes early once the
+/// match is down to a stalemate between a couple of ducks.
+pub fn run_sudden_death(
+    mut sudden_death: ResMut<SuddenDeath>,
+    mut arena: ResMut<Arena>,
+    mut platform_query: Query<&mut Transform, With<Platform>>,
+    contestant_query: Query<&Contestant>,
+    time: Res<Time>,
+    tuning: Res<GameTuning>,
+    settings: Res<GameSettings>,
+) {
+    if !settings.sudden_death {
+        return;
+    }
+    let tuning = &tuning.sudden_death;
+
+    match sudden_death.phase {
+        ShrinkPhase::Waiting => {
+            let left = contestant_query.iter().filter(|contestant| !contestant.is_eliminated()).count();
+            let stalemate = sudden_death.stage == 0 && (2..=tuning.stalemate_ducks).contains(&left);
+            if !sudden_death.timer.tick(time.delta()).finished() && !stalemate {
+                return;
+            }
+
+            let from = arena.scale;
+            let to = (from - tuning.shrink_per_stage).max(tuning.min_scale);
+            if to >= from {
+                sudden_death.phase = ShrinkPhase::Done;
+                return;
+            }
+            sudden_death.stage += 1;
+            sudden_death.from = from;
+            sudden_death.to = to;
+            sudden_death.phase = ShrinkPhase::Warning;
+            sudden_death.timer = Timer::from_seconds(tuning.telegraph, TimerMode::Once);
+            info!("Sudden death stage {}: shrinking to {:.0}%", sudden_death.stage, to * 100.0);
+        }
+        ShrinkPhase::Warning => {
+            if sudden_death.timer.tick(time.delta()).finished() {
+                sudden_death.phase = ShrinkPhase::Shrinking;
+                sudden_death.timer = Timer::from_seconds(tuning.shrink_time, TimerMode::Once);
+            }
+        }
+        ShrinkPhase::Shrinking => {
+            let progress = sudden_death.timer.tick(time.delta()).percent();
+            arena.scale = sudden_death.from + (sudden_death.to - sudden_death.from) * progress;
+            for mut transform in platform_query.iter_mut() {
+                transform.scale = Vec3::new(arena.scale, 1.0, arena.scale);
+            }
+
+            if sudden_death.timer.finished() {
+                sudden_death.phase = if sudden_death.to <= tuning.min_scale {
+                    ShrinkPhase::Done
+                } else {
+                    ShrinkPhase::Waiting
+                };
+                sudden_death.timer = Timer::from_seconds(tuning.stage_interval, TimerMode::Once);
+            }
+        }
+        ShrinkPhase::Done => {}
+    }
+}
+
+/// Marks where the edge will be once the warned stage has shrunk the
+/// platform, blinking faster as the shrink gets closer.
+pub fn draw_shrink_telegraph(sudden_death: Res<SuddenDeath>, arena: Res<Arena>, mut gizmos: Gizmos) {
+    if sudden_death.phase != ShrinkPhase::Warning {
+        return;
+    }
+    let blink_rate = 2.0 + 6.0 * sudden_death.timer.percent();
+    if (sudden_death.timer.elapsed_secs() * blink_rate).fract() > 0.6 {
+        return;
+    }
+
+    for piece in arena.pieces() {
+        let outline = piece.outline();
+        let corners = outline.iter().chain(outline.first()).map(|corner| {
+            let corner = *corner * sudden_death.to;
+            Vec3::new(corner.x, arena.surface_at(corner) + OUTLINE_LIFT, corner.y)
+        });
+        gizmos.linestrip(corners, Color::ORANGE_RED);
+    }
+}
+
+/// Counts down to sudden death and announces each stage.
+pub fn update_sudden_death_text(
+    sudden_death: Res<SuddenDeath>,
+    settings: Res<GameSettings>,
+    mut text_query: Query<&mut Text, With<SuddenDeathText>>,
+) {
+    let remaining = sudden_death.timer.remaining_secs().ceil();
+    let label = match sudden_death.phase {
+        _ if !settings.sudden_death => String::new(),
+        ShrinkPhase::Waiting if sudden_death.stage == 0 && remaining <= COUNTDOWN_WARNING => {
+            format!("Sudden death in {:.0}", remaining)
+        }
+        ShrinkPhase::Waiting if sudden_death.stage == 0 => String::new(),
+        ShrinkPhase::Waiting => format!("Platform shrinks again in {:.0}", remaining),
+        ShrinkPhase::Warning | ShrinkPhase::Shrinking => "Sudden death - the platform is shrinking!".to_string(),
+        ShrinkPhase::Done => "Sudden death".to_string(),
+    };
+    for mut text in text_query.iter_mut() {
+        if text.sections[0].value != label {
+            text.sections[0].value = label.clone();
+        }
+    }
+}
@@ -5,10 +5,25 @@ use crate::components::{
     ScoreText, 
     BoostIndicator, 
     BoostText, 
-    DuckScore
+    Contestant,
+    DuckKnockedOut,
+    GameSettings,
+    Health,
+    HealthBarFill,
+    HealthBarList,
+    HealthBarRow,
+    KnockoutFeedText,
+    Player,
+    PlayerScoreText,
+    MAX_LOCAL_PLAYERS,
+    PLAYER_COLORS,
+    SuddenDeathText,
+    WaveText,
 };
 
-pub fn spawn_hud(mut commands: Commands) {
+pub fn spawn_hud(mut commands: Commands, settings: Res<GameSettings>) {
+    let player_count = settings.player_count.clamp(1, MAX_LOCAL_PLAYERS);
+
     // Root node
     commands
         .spawn((
@@ -47,88 +62,373 @@ pub fn spawn_hud(mut commands: Commands) {
                         ),
                         ScoreText,
                     ));
+
+                    // Difficulty the AI is playing at
+                    parent.spawn(TextBundle::from_section(
+                        format!("Difficulty: {}", settings.difficulty.label()),
+                        TextStyle {
+                            font_size: 16.0,
+                            color: Color::rgb(0.8, 0.8, 0.8),
+                            ..default()
+                        },
+                    ));
+
+                    // Wave counter, filled in by `update_wave_text`
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font_size: 20.0,
+                                color: Color::WHITE,
+                                ..default()
+                            },
+                        ),
+                        WaveText,
+                    ));
+
+                    // Sudden death warnings, filled in by `update_sudden_death_text`
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font_size: 20.0,
+                                color: Color::ORANGE_RED,
+                                ..default()
+                            },
+                        ),
+                        SuddenDeathText,
+                    ));
+
+                    // Who knocked out whom, filled in by `update_knockout_feed`
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font_size: 18.0,
+                                color: Color::rgb(1.0, 0.8, 0.3),
+                                ..default()
+                            },
+                        ),
+                        KnockoutFeedText,
+                    ));
+
+                    // Health bars, filled in as ducks join the match
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Column,
+                                row_gap: Val::Px(4.0),
+                                margin: UiRect::top(Val::Px(10.0)),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        HealthBarList,
+                    ));
                 });
 
-            // Right side - Boost indicator
+            // Right side - one boost indicator per player
             parent
                 .spawn(NodeBundle {
                     style: Style {
-                        width: Val::Px(250.0), // Increased width to accommodate text
-                        height: Val::Px(20.0),
                         position_type: PositionType::Absolute,
                         right: Val::Px(10.0),
                         top: Val::Px(10.0),
-                        flex_direction: FlexDirection::Row,
-                        align_items: AlignItems::Center,
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(8.0),
                         ..default()
                     },
                     ..default()
                 })
                 .with_children(|parent| {
-                    // Boost text
+                    for index in 0..player_count {
+                        spawn_player_boost_row(parent, index, player_count);
+                    }
+                });
+        });
+}
+
+/// Boost bar for one player, labelled and followed by their score in multiplayer.
+fn spawn_player_boost_row(parent: &mut ChildBuilder, index: usize, player_count: usize) {
+    let (label, label_color) = if player_count == 1 {
+        ("Boost:".to_string(), Color::WHITE)
+    } else {
+        (format!("P{}:", index + 1), PLAYER_COLORS[index])
+    };
+
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Px(250.0), // Increased width to accommodate text
+                height: Val::Px(20.0),
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            // Boost text
+            parent.spawn(
+                TextBundle::from_section(
+                    label,
+                    TextStyle {
+                        font_size: 16.0,
+                        color: label_color,
+                        ..default()
+                    }
+                )
+                .with_style(Style {
+                    margin: UiRect::right(Val::Px(10.0)),
+                    ..default()
+                })
+            ).insert(BoostText);
+
+            // Boost background
+            parent.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(200.0),
+                    height: Val::Px(20.0),
+                    ..default()
+                },
+                background_color: Color::rgb(0.96, 0.96, 0.86).into(),
+                ..default()
+            })
+            .with_children(|parent| {
+                // Boost fill
+                parent.spawn(
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        background_color: Color::rgb(0.68, 0.85, 0.90).into(),
+                        ..default()
+                    }
+                ).insert(BoostIndicator { player: index });
+            });
+        });
+
+    // The shared score list covers a lone player
+    if player_count > 1 {
+        parent.spawn((
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: 16.0,
+                    color: label_color,
+                    ..default()
+                },
+            ),
+            PlayerScoreText { player: index },
+        ));
+    }
+}
+
+/// Adds a HUD health bar for every duck in the match and drops bars whose
+/// duck is gone.
+pub fn sync_health_bars(
+    mut commands: Commands,
+    list_query: Query<Entity, With<HealthBarList>>,
+    row_query: Query<(Entity, &HealthBarRow)>,
+    duck_query: Query<(Entity, &Contestant, Option<&Player>), With<Health>>,
+) {
+    let Ok(list) = list_query.get_single() else {
+        return;
+    };
+
+    // Enemies from earlier waves that are out for good drop off the list
+    let listed = |(_, contestant, player): &(Entity, &Contestant, Option<&Player>)| {
+        player.is_some() || !contestant.is_eliminated()
+    };
+
+    for (row, bar) in row_query.iter() {
+        if !duck_query.get(bar.owner).is_ok_and(|duck| listed(&duck)) {
+            commands.entity(row).despawn_recursive();
+        }
+    }
+
+    for (owner, contestant, player) in duck_query.iter().filter(listed) {
+        if row_query.iter().any(|(_, bar)| bar.owner == owner) {
+            continue;
+        }
+
+        let label_color = player.map_or(Color::WHITE, |player| PLAYER_COLORS[player.index % MAX_LOCAL_PLAYERS]);
+        commands.entity(list).with_children(|parent| {
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Px(8.0),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    HealthBarRow { owner },
+                ))
+                .with_children(|parent| {
                     parent.spawn(
                         TextBundle::from_section(
-                            "Boost:",
+                            contestant.name.clone(),
                             TextStyle {
-                                font_size: 16.0,
-                                color: Color::WHITE,
+                                font_size: 14.0,
+                                color: label_color,
                                 ..default()
-                            }
+                            },
                         )
                         .with_style(Style {
-                            margin: UiRect::right(Val::Px(10.0)),
+                            width: Val::Px(70.0),
                             ..default()
-                        })
-                    ).insert(BoostText);
+                        }),
+                    );
 
-                    // Boost background
-                    parent.spawn(NodeBundle {
-                        style: Style {
-                            width: Val::Px(200.0),
-                            height: Val::Px(20.0),
+                    // Health background
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                width: Val::Px(100.0),
+                                height: Val::Px(8.0),
+                                ..default()
+                            },
+                            background_color: Color::rgba(0.2, 0.2, 0.2, 0.8).into(),
                             ..default()
-                        },
-                        background_color: Color::rgb(0.96, 0.96, 0.86).into(),
-                        ..default()
-                    })
-                    .with_children(|parent| {
-                        // Boost fill
-                        parent.spawn(
-                            NodeBundle {
-                                style: Style {
-                                    width: Val::Percent(100.0),
-                                    height: Val::Percent(100.0),
+                        })
+                        .with_children(|parent| {
+                            parent.spawn((
+                                NodeBundle {
+                                    style: Style {
+                                        width: Val::Percent(100.0),
+                                        height: Val::Percent(100.0),
+                                        ..default()
+                                    },
+                                    background_color: Color::GREEN.into(),
                                     ..default()
                                 },
-                                background_color: Color::rgb(0.68, 0.85, 0.90).into(),
-                                ..default()
-                            }
-                        ).insert(BoostIndicator);
-                    });
+                                HealthBarFill { owner },
+                            ));
+                        });
                 });
         });
+    }
+}
+
+/// Sizes each health bar to its duck's health, turning red once the duck is weak.
+pub fn update_health_bars(
+    mut fill_query: Query<(&mut Style, &mut BackgroundColor, &HealthBarFill)>,
+    health_query: Query<(&Health, &Contestant)>,
+) {
+    for (mut style, mut color, fill) in fill_query.iter_mut() {
+        let Ok((health, contestant)) = health_query.get(fill.owner) else {
+            continue;
+        };
+        let fraction = if contestant.is_eliminated() { 0.0 } else { health.fraction() };
+        style.width = Val::Percent(fraction * 100.0);
+        *color = if health.is_weak() {
+            Color::rgb(0.9, 0.2, 0.2).into()
+        } else {
+            Color::rgb(0.3 + 0.6 * (1.0 - fraction), 0.8, 0.3).into()
+        };
+    }
+}
+
+/// Shows each player's points and lives under their boost bar.
+pub fn update_player_score_text(
+    mut text_query: Query<(&mut Text, &PlayerScoreText)>,
+    player_query: Query<(&Player, &Contestant)>,
+) {
+    for (mut text, score_text) in text_query.iter_mut() {
+        if let Some((_, contestant)) = player_query
+            .iter()
+            .find(|(player, _)| player.index == score_text.player)
+        {
+            text.sections[0].value = if contestant.is_eliminated() {
+                format!("{} pts, out", contestant.points)
+            } else if contestant.streak > 1 {
+                format!("{} pts, {} lives, streak {}", contestant.points, contestant.lives, contestant.streak)
+            } else {
+                format!("{} pts, {} lives", contestant.points, contestant.lives)
+            };
+        }
+    }
 }
 
+/// Seconds a knockout stays in the HUD feed.
+const KNOCKOUT_FEED_LIFETIME: f32 = 4.0;
+/// Most knockouts the HUD feed shows at once.
+const KNOCKOUT_FEED_LINES: usize = 4;
+
+/// Announces knockouts in the HUD for a few seconds, with the attacker's
+/// streak once it runs past one.
+pub fn update_knockout_feed(
+    mut knockouts: EventReader<DuckKnockedOut>,
+    contestant_query: Query<&Contestant>,
+    mut text_query: Query<&mut Text, With<KnockoutFeedText>>,
+    time: Res<Time>,
+    mut feed: Local<Vec<(String, f32)>>,
+) {
+    for knockout in knockouts.read() {
+        let (Ok(victim), Ok(attacker)) = (
+            contestant_query.get(knockout.victim),
+            contestant_query.get(knockout.attacker),
+        ) else {
+            continue;
+        };
+        let line = if attacker.streak > 1 {
+            format!("{} knocked out {} ({} in a row)", attacker.name, victim.name, attacker.streak)
+        } else {
+            format!("{} knocked out {}", attacker.name, victim.name)
+        };
+        feed.push((line, KNOCKOUT_FEED_LIFETIME));
+    }
+
+    for (_, remaining) in feed.iter_mut() {
+        *remaining -= time.delta_seconds();
+    }
+    feed.retain(|(_, remaining)| *remaining > 0.0);
+    let excess = feed.len().saturating_sub(KNOCKOUT_FEED_LINES);
+    feed.drain(..excess);
+
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = feed.iter().map(|(line, _)| line.as_str()).collect::<Vec<_>>().join("\n");
+    }
+}
+
+/// Lists every player and every enemy still in the match, ducks still in
+/// the match first, then by points
 pub fn update_score_text(
     mut text_query: Query<&mut Text, With<ScoreText>>,
-    score_query: Query<(&DuckScore, &Transform)>,
+    contestant_query: Query<(&Contestant, Has<Player>)>,
 ) {
     for mut text in text_query.iter_mut() {
-        let mut scores = score_query
+        let mut contestants = contestant_query
             .iter()
-            .map(|(score, transform)| (score.name.clone(), score.value, transform.translation.y))
-        .collect::<Vec<_>>();
-        
-        // Sort by score value (descending) and filter out fallen ducks
-        scores.sort_by(|a, b| b.1.cmp(&a.1));
-        scores.retain(|(_name, _score, y)| *y > -5.0);
+            .filter(|(contestant, is_player)| *is_player || !contestant.is_eliminated())
+            .map(|(contestant, _)| contestant)
+            .collect::<Vec<_>>();
+        contestants.sort_by(|a, b| {
+            a.is_eliminated()
+                .cmp(&b.is_eliminated())
+                .then(b.points.cmp(&a.points))
+        });
 
         // Update text
         text.sections[0].value = format!(
             "Scores:\n{}",
-            scores
+            contestants
                 .iter()
-                .map(|(name, score, _)| format!("{}: {}", name, score))
+                .map(|contestant| {
+                    if contestant.is_eliminated() {
+                        format!("{}: {} pts, out", contestant.name, contestant.points)
+                    } else {
+                        format!(
+                            "{}: {} pts, {} lives",
+                            contestant.name, contestant.points, contestant.lives
+                        )
+                    }
+                })
                 .collect::<Vec<_>>()
                 .join("\n")
         );
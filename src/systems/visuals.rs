@@ -0,0 +1,153 @@
+use bevy::prelude::*;
+
+use crate::components::{DuckModel, GameSettings, PlatformEdge, PlatformPiece, PowerUpType};
+use crate::resources::PieceShape;
+use crate::systems::PowerUpCoin;
+
+/// Spawns the light and the 3D camera for a match.
+pub fn setup_scene(mut commands: Commands, settings: Res<GameSettings>) {
+    // Light
+    commands.spawn(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            shadows_enabled: settings.shadows,
+            ..default()
+        },
+        transform: Transform::from_xyz(-15.0, 20.0, 15.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+
+    // Camera
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(-15.0, 20.0, 15.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+}
+
+/// Mesh of a platform piece `thickness` high.
+fn piece_mesh(shape: PieceShape, thickness: f32) -> Mesh {
+    match shape {
+        PieceShape::Disc { radius, sides } => shape::Cylinder {
+            radius,
+            height: thickness,
+            resolution: sides.map_or(shape::Cylinder::default().resolution, |sides| sides as u32),
+            ..default()
+        }
+        .into(),
+        PieceShape::Slab { half_extents } => {
+            shape::Box::new(half_extents.x * 2.0, thickness, half_extents.y * 2.0).into()
+        }
+    }
+}
+
+/// Gives newly spawned platform pieces their meshes.
+pub fn attach_platform_visuals(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    piece_query: Query<(Entity, &PlatformPiece), Added<PlatformPiece>>,
+    edge_query: Query<(Entity, &PlatformEdge), Added<PlatformEdge>>,
+) {
+    for (entity, piece) in piece_query.iter() {
+        commands.entity(entity).insert((
+            meshes.add(piece_mesh(piece.shape, piece.thickness)),
+            materials.add(StandardMaterial {
+                base_color: Color::rgb(0.3, 0.5, 0.3),
+                ..default()
+            }),
+        ));
+    }
+
+    for (entity, edge) in edge_query.iter() {
+        commands.entity(entity).insert((
+            meshes.add(piece_mesh(edge.shape, edge.thickness)),
+            materials.add(StandardMaterial {
+                base_color: Color::rgb(0.8, 0.6, 0.2),
+                ..default()
+            }),
+        ));
+    }
+}
+
+/// Builds the body, head and bill meshes for newly spawned ducks.
+pub fn attach_duck_visuals(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    duck_query: Query<(Entity, &DuckModel), Added<DuckModel>>,
+) {
+    for (entity, model) in duck_query.iter() {
+        let body_mesh = shape::UVSphere {
+            radius: model.body_radius,
+            ..default()
+        };
+
+        let head_mesh = shape::UVSphere {
+            radius: model.head_radius,
+            ..default()
+        };
+
+        let bill_mesh = shape::Box {
+            min_x: 0.0,
+            max_x: model.bill_length,
+            min_y: -model.head_radius / 2.0,
+            max_y: model.head_radius / 2.0,
+            min_z: -model.head_radius / 2.0,
+            max_z: model.head_radius / 2.0,
+        };
+
+        commands.entity(entity).insert((
+            meshes.add(body_mesh.into()),
+            materials.add(StandardMaterial {
+                base_color: model.base_color,
+                ..default()
+            }),
+        ));
+
+        commands.entity(entity).with_children(|parent| {
+            // Spawn head
+            parent.spawn(PbrBundle {
+                mesh: meshes.add(head_mesh.into()),
+                material: materials.add(StandardMaterial {
+                    base_color: model.base_color,
+                    ..default()
+                }),
+                transform: Transform::from_translation(model.body_offset + model.head_offset),
+                ..default()
+            });
+
+            // Spawn bill
+            parent.spawn(PbrBundle {
+                mesh: meshes.add(bill_mesh.into()),
+                material: materials.add(StandardMaterial {
+                    base_color: model.bill_color,
+                    ..default()
+                }),
+                transform: Transform::from_translation(model.body_offset + model.head_offset + model.bill_offset),
+                ..default()
+            });
+        });
+    }
+}
+
+/// Gives newly spawned powerup coins their mesh and metallic material.
+pub fn attach_powerup_coin_visuals(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    coin_query: Query<(Entity, &PowerUpCoin), Added<PowerUpCoin>>,
+) {
+    for (entity, coin) in coin_query.iter() {
+        commands.entity(entity).insert((
+            meshes.add(shape::Box::new(0.5, 0.5, 0.5).into()),
+            materials.add(StandardMaterial {
+                base_color: match coin.power_type {
+                    PowerUpType::Grow => Color::rgb(1.0, 0.8, 0.0),
+                    PowerUpType::Shrink => Color::rgb(0.0, 0.8, 1.0),
+                },
+                metallic: 1.0,
+                perceptual_roughness: 0.1,
+                ..default()
+            }),
+        ));
+    }
+}
@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+
+use crate::components::{Contestant, Enemy, GameSettings, WaveText};
+use crate::resources::{Arena, GameRng, GameTuning, RngStream};
+use crate::systems::spawn_enemy;
+
+/// How far apart enemies sharing a spawn point are placed.
+const CROWDED_SPAWN_SPACING: f32 = 1.5;
+
+/// Where the wave director is between waves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WavePhase {
+    /// Calm before the next wave is announced
+    Breather,
+    /// Spawn points are marked; enemies appear when the timer runs out
+    Incoming,
+    /// The wave's enemies are on the platform
+    Fighting,
+    /// Every wave has been cleared
+    Cleared,
+}
+
+/// Brings enemies in wave by wave, as laid out in the tuning file.
+#[derive(Resource, Debug)]
+pub struct WaveDirector {
+    /// 1-based number of the current wave; 0 before the first one
+    pub wave: usize,
+    pub phase: WavePhase,
+    /// Runs the breather and the spawn telegraph
+    pub timer: Timer,
+    /// Enemies spawned this match, for naming the next one
+    pub spawned: usize,
+}
+
+impl Default for WaveDirector {
+    fn default() -> Self {
+        Self {
+            wave: 0,
+            phase: WavePhase::Breather,
+            timer: Timer::from_seconds(0.0, TimerMode::Once),
+            spawned: 0,
+        }
+    }
+}
+
+/// A spot where an enemy of the incoming wave is about to appear.
+#[derive(Component, Debug)]
+pub struct PendingSpawn {
+    pub position: Vec2,
+}
+
+/// Starts a fresh match at the breather before the first wave.
+pub fn reset_wave_director(mut director: ResMut<WaveDirector>, tuning: Res<GameTuning>) {
+    *director = WaveDirector {
+        timer: Timer::from_seconds(tuning.wave.first_delay, TimerMode::Once),
+        ..default()
+    };
+}
+
+/// Steps through breather, telegraph and fight for each wave. A wave is
+/// cleared once every enemy on the platform is out of lives; clearing the
+/// last one clears the match. With no waves listed no enemies come and the
+/// match is never cleared.
+pub fn run_wave_director(
+    mut commands: Commands,
+    mut director: ResMut<WaveDirector>,
+    pending_query: Query<(Entity, &PendingSpawn)>,
+    enemy_query: Query<&Contestant, With<Enemy>>,
+    arena: Res<Arena>,
+    time: Res<Time>,
+    mut game_rng: ResMut<GameRng>,
+    tuning: Res<GameTuning>,
+    settings: Res<GameSettings>,
+) {
+    let waves = &tuning.wave.waves;
+    match director.phase {
+        WavePhase::Breather => {
+            if !director.timer.tick(time.delta()).finished() {
+                return;
+            }
+            let Some(wave) = waves.get(director.wave) else {
+                if director.wave > 0 {
+                    director.phase = WavePhase::Cleared;
+                }
+                return;
+            };
+
+            // Mark where the wave comes in, spreading it over the spawn points
+            let rng = game_rng.stream(RngStream::Spawning);
+            let mut points = arena.enemy_spawn_points();
+            if points.is_empty() {
+                points.push(arena.home(Vec2::ZERO));
+            }
+            points.shuffle(rng);
+            for index in 0..wave.enemies {
+                let point = points[index % points.len()];
+                let crowd = (index / points.len()) as f32;
+                let inward = (arena.home(point) - point).normalize_or_zero();
+                let position = point + inward * crowd * CROWDED_SPAWN_SPACING;
+                commands.spawn(PendingSpawn { position });
+            }
+
+            director.wave += 1;
+            director.phase = WavePhase::Incoming;
+            director.timer = Timer::from_seconds(tuning.wave.telegraph, TimerMode::Once);
+            info!("Wave {} incoming: {} enemies", director.wave, wave.enemies);
+        }
+        WavePhase::Incoming => {
+            if !director.timer.tick(time.delta()).finished() {
+                return;
+            }
+            let Some(wave) = waves.get(director.wave - 1).or(waves.last()) else {
+                director.phase = WavePhase::Cleared;
+                return;
+            };
+
+            let rng = game_rng.stream(RngStream::Spawning);
+            for (entity, pending) in pending_query.iter() {
+                director.spawned += 1;
+                let name = format!("Enemy {}", director.spawned);
+                let position = arena.spawn_position(pending.position);
+                spawn_enemy(&mut commands, rng, &tuning, settings.difficulty, wave, name, position);
+                commands.entity(entity).despawn_recursive();
+            }
+            director.phase = WavePhase::Fighting;
+        }
+        WavePhase::Fighting => {
+            if !enemy_query.iter().all(|contestant| contestant.is_eliminated()) {
+                return;
+            }
+            info!("Wave {} cleared", director.wave);
+            if director.wave >= waves.len() {
+                director.phase = WavePhase::Cleared;
+            } else {
+                director.phase = WavePhase::Breather;
+                director.timer = Timer::from_seconds(tuning.wave.breather, TimerMode::Once);
+            }
+        }
+        WavePhase::Cleared => {}
+    }
+}
+
+/// Marks the spawn points of an incoming wave with rings that close in as
+/// the enemies are about to appear.
+pub fn draw_spawn_telegraphs(
+    director: Res<WaveDirector>,
+    pending_query: Query<&PendingSpawn>,
+    arena: Res<Arena>,
+    mut gizmos: Gizmos,
+) {
+    let progress = director.timer.percent();
+    for pending in pending_query.iter() {
+        let ground = Vec3::new(pending.position.x, arena.surface_at(pending.position) + 0.05, pending.position.y);
+        gizmos.circle(ground, Vec3::Y, 2.0 - 1.4 * progress, Color::ORANGE_RED);
+        gizmos.circle(ground, Vec3::Y, 0.6, Color::ORANGE_RED);
+        gizmos.line(ground, ground + Vec3::Y * 3.0 * (1.0 - progress), Color::ORANGE_RED);
+    }
+}
+
+/// Shows the wave number, and what comes next between waves.
+pub fn update_wave_text(
+    director: Res<WaveDirector>,
+    tuning: Res<GameTuning>,
+    mut text_query: Query<&mut Text, With<WaveText>>,
+) {
+    let total = tuning.wave.waves.len();
+    let label = match director.phase {
+        WavePhase::Breather if total == 0 => String::new(),
+        WavePhase::Breather if director.wave == 0 => "Get ready!".to_string(),
+        WavePhase::Breather => format!(
+            "Wave {}/{} cleared - next in {:.0}",
+            director.wave,
+            total,
+            director.timer.remaining_secs().ceil()
+        ),
+        WavePhase::Incoming => format!("Wave {}/{} incoming!", director.wave, total),
+        WavePhase::Fighting => format!("Wave {}/{}", director.wave, total),
+        WavePhase::Cleared => "All waves cleared".to_string(),
+    };
+    for mut text in text_query.iter_mut() {
+        if text.sections[0].value != label {
+            text.sections[0].value = label.clone();
+        }
+    }
+}
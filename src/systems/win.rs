@@ -1,17 +1,14 @@
 use bevy::prelude::*;
-use crate::components::Enemy;
+use crate::resources::MatchResult;
 use crate::states::GameState;
+use crate::systems::{new_record_banner, WaveDirector, WavePhase};
 
+/// The match is won once the last wave has been cleared.
 pub fn check_win_condition(
-    enemy_query: Query<&Enemy>,
+    director: Res<WaveDirector>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
-    // Check if all enemies have been defeated (score <= 0)
-    let enemies: Vec<&Enemy> = enemy_query.iter().collect();
-    let all_enemies_defeated = enemies.iter()
-        .all(|enemy| enemy.is_fallen);
-
-    if all_enemies_defeated {
+    if director.phase == WavePhase::Cleared {
         next_state.set(GameState::WinScreen);
     }
 }
@@ -20,6 +17,7 @@ pub fn spawn_win_screen(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     camera_query: Query<Entity, With<Camera>>,
+    result: Res<MatchResult>,
 ) {
     // Despawn existing cameras
     for camera_entity in camera_query.iter() {
@@ -56,6 +54,10 @@ pub fn spawn_win_screen(
             }
         ));
 
+        if result.new_record {
+            parent.spawn(new_record_banner(&asset_server));
+        }
+
         // Restart button
         parent.spawn((
             ButtonBundle {